@@ -1,6 +1,8 @@
 use std::env;
 use std::fs;
 use std::path::PathBuf;
+use std::process::Command;
+use std::str;
 
 const PRIVATE: &str = "\
 #[doc(hidden)]
@@ -17,4 +19,23 @@ fn main() {
     let patch_version = env::var("CARGO_PKG_VERSION_PATCH").unwrap();
     let module = PRIVATE.replace("$$", &patch_version);
     fs::write(out_dir.join("private.rs"), module).unwrap();
+
+    let Some(rustc) = rustc_minor_version() else {
+        return;
+    };
+
+    if rustc >= 80 {
+        println!("cargo:rustc-check-cfg=cfg(linkme_fallback)");
+    }
+}
+
+fn rustc_minor_version() -> Option<u32> {
+    let rustc = env::var_os("RUSTC").unwrap();
+    let output = Command::new(rustc).arg("--version").output().ok()?;
+    let version = str::from_utf8(&output.stdout).ok()?;
+    let mut pieces = version.split('.');
+    if pieces.next() != Some("rustc 1") {
+        return None;
+    }
+    pieces.next()?.parse().ok()
 }
@@ -11,6 +11,7 @@ fn main() {
 
     if rustc >= 80 {
         println!("cargo:rustc-check-cfg=cfg(exhaustive)");
+        println!("cargo:rustc-check-cfg=cfg(linkme_fallback)");
         println!("cargo:rustc-check-cfg=cfg(no_unsafe_attributes)");
         println!("cargo:rustc-check-cfg=cfg(no_unsafe_extern_blocks)");
     }
@@ -1,27 +1,208 @@
 use syn::parse::{Error, Parse, ParseStream, Result};
-use syn::{LitInt, Path, Token};
+use syn::{parenthesized, token, Expr, Ident, LitInt, LitStr, Path, Token, Type};
 
 pub enum Args {
-    None,
-    Path(Path),
-    PathPos(Path, usize),
+    Declaration(Cardinality),
+    ExternSection(LitStr, Cardinality),
+    Element(ElementArgs),
+}
+
+// [min = N] [, max = N] | exact = N
+#[derive(Copy, Clone, Default)]
+pub struct Cardinality {
+    pub min: usize,
+    pub max: Option<usize>,
+}
+
+// PATH, ... [, POS] [, instantiate(TYPE, ...)]
+pub struct ElementArgs {
+    pub paths: Vec<Path>,
+    pub pos: Option<usize>,
+    pub instantiate: Vec<Type>,
 }
 
 impl Parse for Args {
     fn parse(input: ParseStream) -> Result<Self> {
         if input.is_empty() {
-            return Ok(Args::None);
+            return Ok(Args::Declaration(Cardinality::default()));
+        }
+        if input.peek(Token![unsafe]) {
+            let section = parse_extern_section(input)?;
+            let cardinality = parse_cardinality(input)?;
+            return Ok(Args::ExternSection(section, cardinality));
+        }
+        if input.peek(Ident) && input.peek2(Token![=]) {
+            let ident: Ident = input.fork().parse()?;
+            if ident == "extern_section" {
+                return Err(Error::new(
+                    ident.span(),
+                    "extern_section must be written as `unsafe(extern_section = \"...\")`",
+                ));
+            }
+            if ident == "min" || ident == "max" || ident == "exact" {
+                return parse_cardinality(input).map(Args::Declaration);
+            }
+        }
+        let mut args = ElementArgs {
+            paths: vec![input.parse()?],
+            pos: None,
+            instantiate: Vec::new(),
+        };
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.peek(LitInt) && args.pos.is_none() && args.instantiate.is_empty() {
+                let lit: LitInt = input.parse()?;
+                let pos: usize = lit.base10_parse()?;
+                if pos > 9999 {
+                    return Err(Error::new(lit.span(), "maximum 9999 is supported"));
+                }
+                args.pos = Some(pos);
+            } else if input.peek(Ident) && input.peek2(token::Paren) && args.instantiate.is_empty()
+            {
+                let instantiate: Ident = input.parse()?;
+                if instantiate != "instantiate" {
+                    return Err(Error::new(instantiate.span(), "expected `instantiate`"));
+                }
+                let content;
+                parenthesized!(content in input);
+                let types = content.parse_terminated(Type::parse, Token![,])?;
+                if types.is_empty() {
+                    return Err(Error::new(instantiate.span(), "expected at least one type"));
+                }
+                args.instantiate = Vec::from_iter(types);
+            } else if args.pos.is_none() && args.instantiate.is_empty() {
+                args.paths.push(input.parse()?);
+            } else {
+                return Err(input.error("unexpected argument"));
+            }
+        }
+        Ok(Args::Element(args))
+    }
+}
+
+// unsafe(extern_section = "...")
+fn parse_extern_section(input: ParseStream) -> Result<LitStr> {
+    input.parse::<Token![unsafe]>()?;
+    let content;
+    parenthesized!(content in input);
+    let extern_section: Ident = content.parse()?;
+    if extern_section != "extern_section" {
+        return Err(Error::new(
+            extern_section.span(),
+            "expected `extern_section`",
+        ));
+    }
+    content.parse::<Token![=]>()?;
+    let section: LitStr = content.parse()?;
+    if section.value().is_empty() {
+        return Err(Error::new(section.span(), "section name must not be empty"));
+    }
+    content.parse::<Option<Token![,]>>()?;
+    if !content.is_empty() {
+        return Err(content.error("unexpected argument"));
+    }
+    input.parse::<Option<Token![,]>>()?;
+    Ok(section)
+}
+
+fn parse_cardinality(input: ParseStream) -> Result<Cardinality> {
+    let mut min = None;
+    let mut max = None;
+    let mut exact = None;
+    while !input.is_empty() {
+        let ident: Ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        let lit: LitInt = input.parse()?;
+        let value: usize = lit.base10_parse()?;
+        let slot = if ident == "min" {
+            &mut min
+        } else if ident == "max" {
+            &mut max
+        } else if ident == "exact" {
+            &mut exact
+        } else {
+            return Err(Error::new(
+                ident.span(),
+                "expected `min`, `max`, or `exact`",
+            ));
+        };
+        if slot.is_some() {
+            let msg = format!("duplicate {} argument", ident);
+            return Err(Error::new(ident.span(), msg));
+        }
+        *slot = Some((value, lit));
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+    }
+
+    if let Some((exact, lit)) = exact {
+        if min.is_some() || max.is_some() {
+            return Err(Error::new(
+                lit.span(),
+                "exact cannot be combined with min or max",
+            ));
+        }
+        return Ok(Cardinality {
+            min: exact,
+            max: Some(exact),
+        });
+    }
+    let min = min.map_or(0, |(min, _lit)| min);
+    if let Some((max, lit)) = &max {
+        if *max < min {
+            return Err(Error::new(lit.span(), "max must not be less than min"));
+        }
+    }
+    Ok(Cardinality {
+        min,
+        max: max.map(|(max, _lit)| max),
+    })
+}
+
+pub enum MapArgs {
+    None,
+    PathKey(Path, Box<Expr>),
+}
+
+impl Parse for MapArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.is_empty() {
+            return Ok(MapArgs::None);
         }
         let path: Path = input.parse()?;
         if input.is_empty() {
-            return Ok(Args::Path(path));
+            return Err(input.error("expected `, key = ...`"));
         }
         input.parse::<Token![,]>()?;
-        let lit: LitInt = input.parse()?;
-        let pos: usize = lit.base10_parse()?;
-        if pos > 9999 {
-            return Err(Error::new(lit.span(), "maximum 9999 is supported"));
+        let key_ident: Ident = input.parse()?;
+        if key_ident != "key" {
+            return Err(Error::new(key_ident.span(), "expected `key`"));
+        }
+        input.parse::<Token![=]>()?;
+        let key: Box<Expr> = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(MapArgs::PathKey(path, key))
+    }
+}
+
+pub enum SlotArgs {
+    None,
+    Path(Path),
+    PathOverride(Path),
+}
+
+impl Parse for SlotArgs {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.is_empty() {
+            return Ok(SlotArgs::None);
+        }
+        let path: Path = input.parse()?;
+        if input.is_empty() {
+            return Ok(SlotArgs::Path(path));
         }
-        Ok(Args::PathPos(path, pos))
+        input.parse::<Token![,]>()?;
+        input.parse::<Token![override]>()?;
+        Ok(SlotArgs::PathOverride(path))
     }
 }
@@ -1,9 +1,28 @@
 use syn::parse::{Error, Result};
-use syn::{parse_quote, Attribute, Path};
+use syn::{parse_quote, Attribute, LitStr, Path};
+
+pub(crate) struct DeclarationOptions {
+    pub linkme_path: Path,
+    pub element_info: bool,
+    pub force_link: bool,
+    pub section: Option<LitStr>,
+}
 
 // #[linkme(crate = path::to::linkme)]
 pub(crate) fn linkme_path(attrs: &mut Vec<Attribute>) -> Result<Path> {
+    parse(attrs, false).map(|options| options.linkme_path)
+}
+
+// #[linkme(crate = path::to::linkme, element_info, force_link, section = "...")]
+pub(crate) fn declaration_options(attrs: &mut Vec<Attribute>) -> Result<DeclarationOptions> {
+    parse(attrs, true)
+}
+
+fn parse(attrs: &mut Vec<Attribute>, declaration: bool) -> Result<DeclarationOptions> {
     let mut linkme_path = None;
+    let mut element_info = false;
+    let mut force_link = false;
+    let mut section = None;
     let mut errors: Option<Error> = None;
 
     attrs.retain(|attr| {
@@ -18,6 +37,28 @@ pub(crate) fn linkme_path(attrs: &mut Vec<Attribute>) -> Result<Path> {
                 let path = meta.value()?.call(Path::parse_mod_style)?;
                 linkme_path = Some(path);
                 Ok(())
+            } else if declaration && meta.path.is_ident("element_info") {
+                if element_info {
+                    return Err(meta.error("duplicate linkme element_info attribute"));
+                }
+                element_info = true;
+                Ok(())
+            } else if declaration && meta.path.is_ident("force_link") {
+                if force_link {
+                    return Err(meta.error("duplicate linkme force_link attribute"));
+                }
+                force_link = true;
+                Ok(())
+            } else if declaration && meta.path.is_ident("section") {
+                if section.is_some() {
+                    return Err(meta.error("duplicate linkme section attribute"));
+                }
+                let name: LitStr = meta.value()?.parse()?;
+                if name.value().is_empty() {
+                    return Err(Error::new(name.span(), "section name must not be empty"));
+                }
+                section = Some(name);
+                Ok(())
             } else {
                 Err(meta.error("unsupported linkme attribute"))
             }
@@ -31,7 +72,12 @@ pub(crate) fn linkme_path(attrs: &mut Vec<Attribute>) -> Result<Path> {
     });
 
     match errors {
-        None => Ok(linkme_path.unwrap_or_else(|| parse_quote!(::linkme))),
+        None => Ok(DeclarationOptions {
+            linkme_path: linkme_path.unwrap_or_else(|| parse_quote!(::linkme)),
+            element_info,
+            force_link,
+            section,
+        }),
         Some(errors) => Err(errors),
     }
 }
@@ -1,14 +1,27 @@
+use crate::args::Cardinality;
+use crate::section::Role;
 use crate::{attr, linker, private, ty};
-use proc_macro2::{Span, TokenStream};
+use proc_macro2::{Delimiter, Span, TokenStream, TokenTree};
 use quote::quote;
 use syn::parse::{Parse, ParseStream, Result};
-use syn::{bracketed, Attribute, Error, Ident, Token, Type, Visibility};
+use syn::{
+    parse_quote, Attribute, Error, GenericArgument, Ident, LitStr, Path, PathArguments, Token,
+    Type, Visibility,
+};
+
+#[derive(Copy, Clone, PartialEq)]
+enum Kind {
+    Slice,
+    Map,
+    Slot,
+}
 
 struct Declaration {
     attrs: Vec<Attribute>,
     vis: Visibility,
     ident: Ident,
     ty: Type,
+    default: Option<TokenStream>,
 }
 
 impl Parse for Declaration {
@@ -28,25 +41,154 @@ impl Parse for Declaration {
         let ty: Type = input.parse()?;
 
         let eq_token: Option<Token![=]> = input.parse()?;
+        let mut default = None;
         if eq_token.is_some() {
-            let content;
-            bracketed!(content in input);
-            content.parse::<Token![..]>()?;
+            let mut expr_semi = Vec::from_iter(input.parse::<TokenStream>()?);
+            if let Some(tail) = expr_semi.pop() {
+                syn::parse2::<Token![;]>(TokenStream::from(tail))?;
+            }
+            let expr = TokenStream::from_iter(expr_semi);
+            if !is_legacy_initializer(&expr) {
+                default = Some(expr);
+            }
+        } else {
+            input.parse::<Token![;]>()?;
         }
 
-        input.parse::<Token![;]>()?;
-
         Ok(Declaration {
             attrs,
             vis,
             ident,
             ty,
+            default,
         })
     }
 }
 
-pub fn expand(input: TokenStream) -> TokenStream {
-    let msg = "distributed_slice is not implemented for this platform";
+// [..]
+fn is_legacy_initializer(expr: &TokenStream) -> bool {
+    let mut tokens = expr.clone().into_iter();
+    match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Group(group)), None) => {
+            group.delimiter() == Delimiter::Bracket
+                && syn::parse2::<Token![..]>(group.stream()).is_ok()
+        }
+        _ => false,
+    }
+}
+
+pub fn expand(cardinality: Cardinality, input: TokenStream) -> TokenStream {
+    do_expand(input, Kind::Slice, None, cardinality)
+}
+
+pub fn expand_extern(section: LitStr, cardinality: Cardinality, input: TokenStream) -> TokenStream {
+    do_expand(input, Kind::Slice, Some(section), cardinality)
+}
+
+pub fn expand_map(input: TokenStream) -> TokenStream {
+    do_expand(input, Kind::Map, None, Cardinality::default())
+}
+
+pub fn expand_slot(input: TokenStream) -> TokenStream {
+    do_expand(input, Kind::Slot, None, Cardinality::default())
+}
+
+// Map<K, V>
+fn map_types(ty: &Type) -> Result<(Type, Type)> {
+    let error = || Error::new_spanned(ty, "distributed map must have type Map<K, V>");
+
+    let Type::Path(path) = ty else {
+        return Err(error());
+    };
+    if path.qself.is_some() || path.path.segments.len() != 1 {
+        return Err(error());
+    }
+    let segment = &path.path.segments[0];
+    let PathArguments::AngleBracketed(generics) = &segment.arguments else {
+        return Err(error());
+    };
+    if segment.ident != "Map" || generics.args.len() != 2 {
+        return Err(error());
+    }
+    match (&generics.args[0], &generics.args[1]) {
+        (GenericArgument::Type(key), GenericArgument::Type(value)) => {
+            Ok((key.clone(), value.clone()))
+        }
+        _ => Err(error()),
+    }
+}
+
+// The linker synthesizes __start_ and __stop_ symbols on ELF targets only for
+// sections whose name is a C identifier, and illumos only for sections named
+// set_*. Mach-O limits section names to 16 bytes. PE/COFF groups sections by
+// the part of the name before `$`. Each violation is reported only when
+// compiling for the affected platform.
+fn validate_section(section: &LitStr) -> TokenStream {
+    let name = section.value();
+    let mut errors = TokenStream::new();
+    let mut error = |cfg: TokenStream, msg: &str| {
+        let error = Error::new(section.span(), msg).to_compile_error();
+        errors.extend(quote!(#[cfg(#cfg)] #error));
+    };
+
+    let mut chars = name.chars();
+    let is_c_identifier = chars
+        .next()
+        .is_some_and(|ch| ch == '_' || ch.is_ascii_alphabetic())
+        && chars.all(|ch| ch == '_' || ch.is_ascii_alphanumeric());
+    if !is_c_identifier {
+        error(
+            quote! {
+                any(
+                    target_os = "none",
+                    target_os = "linux",
+                    target_os = "android",
+                    target_os = "fuchsia",
+                    target_os = "illumos",
+                    target_os = "freebsd",
+                    target_os = "openbsd",
+                    target_os = "psp",
+                )
+            },
+            "section name must be a C identifier on ELF targets",
+        );
+    }
+    if !name.starts_with("set_") {
+        error(
+            quote!(target_os = "illumos"),
+            "section name must start with `set_` on illumos",
+        );
+    }
+    if name.len() > 16 || name.contains(',') {
+        error(
+            quote!(any(
+                target_os = "macos",
+                target_os = "ios",
+                target_os = "tvos"
+            )),
+            "section name must be at most 16 bytes and contain no `,` on Mach-O targets",
+        );
+    }
+    if name.contains('$') {
+        error(
+            quote!(any(target_os = "uefi", target_os = "windows")),
+            "section name must not contain `$` on PE/COFF targets",
+        );
+    }
+    errors
+}
+
+fn do_expand(
+    input: TokenStream,
+    kind: Kind,
+    extern_section: Option<LitStr>,
+    cardinality: Cardinality,
+) -> TokenStream {
+    let msg = match kind {
+        Kind::Slice => "distributed_slice is not implemented for this platform",
+        Kind::Map => "distributed_map is not implemented for this platform",
+        Kind::Slot => "distributed_slot is not implemented for this platform",
+    };
     let error = Error::new_spanned(&input, msg);
     let unsupported_platform = error.to_compile_error();
 
@@ -55,84 +197,210 @@ pub fn expand(input: TokenStream) -> TokenStream {
         Err(err) => return err.to_compile_error(),
     };
 
+    let default = match (kind, &decl.default) {
+        (Kind::Slot, None) => return expand_required_slot(decl),
+        (Kind::Slot, Some(default)) => default.clone(),
+        (Kind::Slice | Kind::Map, None) => TokenStream::new(),
+        (Kind::Slice | Kind::Map, Some(default)) => {
+            let msg = "expected `[..]`";
+            return Error::new_spanned(default, msg).to_compile_error();
+        }
+    };
+
     let mut attrs = decl.attrs;
     let vis = decl.vis;
     let ident = decl.ident;
     let mut ty = decl.ty;
     let name = ident.to_string();
 
-    let linkme_path = match attr::linkme_path(&mut attrs) {
-        Ok(path) => path,
+    let options = match attr::declaration_options(&mut attrs) {
+        Ok(options) => options,
         Err(err) => return err.to_compile_error(),
     };
+    let linkme_path = options.linkme_path;
+    if let Some(extern_section) = &extern_section {
+        if options.force_link {
+            let msg = "force_link is not supported on an extern section";
+            return Error::new(extern_section.span(), msg).to_compile_error();
+        }
+        if options.element_info {
+            let msg = "element_info is not supported on an extern section";
+            return Error::new(extern_section.span(), msg).to_compile_error();
+        }
+        if let Some(section) = &options.section {
+            let msg = "section is already named by extern_section";
+            return Error::new(section.span(), msg).to_compile_error();
+        }
+    }
+    let section = extern_section.as_ref().or(options.section.as_ref());
 
     ty::populate_static_lifetimes(&mut ty);
 
-    let used = if cfg!(feature = "used_linker") {
-        quote!(#[used(linker)])
-    } else {
-        quote!(#[used])
-    };
+    let mut static_ty = quote!(#linkme_path::DistributedSlice<#ty>);
+    let mut type_alias = None;
+    let mut override_symbol = None;
+    match kind {
+        // The element type is exported under a name derived from the slice's
+        // for the benefit of anonymous elements.
+        Kind::Slice => {
+            let alias = ty::element_alias(&ident);
+            type_alias = Some(quote! {
+                #[doc(hidden)]
+                #[allow(dead_code, non_camel_case_types)]
+                #vis type #alias = <#ty as #linkme_path::#private::Slice>::Element;
+            });
+        }
+        // A distributed map is stored as a distributed slice of map entries.
+        // The entry type is exported under a name derived from the map's so
+        // that elements in downstream crates can name it.
+        Kind::Map => {
+            let (key, value) = match map_types(&ty) {
+                Ok(types) => types,
+                Err(err) => return err.to_compile_error(),
+            };
+            if options.force_link {
+                let msg = "force_link is not supported on distributed_map";
+                return Error::new_spanned(&ident, msg).to_compile_error();
+            }
+            if options.element_info {
+                let msg = "element_info is not supported on distributed_map";
+                return Error::new_spanned(&ident, msg).to_compile_error();
+            }
+            static_ty = quote!(#linkme_path::DistributedMap<#key, #value>);
+            ty = parse_quote!([#linkme_path::#private::MapEntry<#key, #value>]);
+            let alias = ty::element_alias(&ident);
+            type_alias = Some(quote! {
+                #[doc(hidden)]
+                #[allow(non_camel_case_types)]
+                #vis type #alias = #linkme_path::#private::MapEntry<#key, #value>;
+            });
+        }
+        // A distributed slot with a default value is stored as the default
+        // plus a distributed slice of overrides. Each override also exports a
+        // strong symbol so that a second override is a link error.
+        Kind::Slot => {
+            if options.force_link {
+                let msg = "force_link is not supported on distributed_slot";
+                return Error::new_spanned(&ident, msg).to_compile_error();
+            }
+            if options.element_info {
+                let msg = "element_info is not supported on distributed_slot";
+                return Error::new_spanned(&ident, msg).to_compile_error();
+            }
+            static_ty = quote!(#linkme_path::DistributedSlot<#ty>);
+            override_symbol = Some(linker::symbol::slot_override(&ident));
+            ty = parse_quote!([#ty]);
+        }
+    }
+
+    let used = used();
+
+    let mut linux_section = linker::linux::section(Role::Elements, &ident);
+    let mut linux_section_start = linker::linux::section_start(Role::Elements, &ident);
+    let mut linux_section_stop = linker::linux::section_stop(Role::Elements, &ident);
+    let linux_dupcheck = linker::linux::section(Role::Dupcheck, &ident);
+    let linux_dupcheck_start = linker::linux::section_start(Role::Dupcheck, &ident);
+    let linux_dupcheck_stop = linker::linux::section_stop(Role::Dupcheck, &ident);
+    let linux_info = linker::linux::section(Role::Info, &ident);
+    let linux_info_start = linker::linux::section_start(Role::Info, &ident);
+    let linux_info_stop = linker::linux::section_stop(Role::Info, &ident);
+
+    let mut macho_section = linker::macho::section(Role::Elements, &ident);
+    let mut macho_section_start = linker::macho::section_start(Role::Elements, &ident);
+    let mut macho_section_stop = linker::macho::section_stop(Role::Elements, &ident);
+    let macho_dupcheck = linker::macho::section(Role::Dupcheck, &ident);
+    let macho_dupcheck_start = linker::macho::section_start(Role::Dupcheck, &ident);
+    let macho_dupcheck_stop = linker::macho::section_stop(Role::Dupcheck, &ident);
+    let macho_info = linker::macho::section(Role::Info, &ident);
+    let macho_info_start = linker::macho::section_start(Role::Info, &ident);
+    let macho_info_stop = linker::macho::section_stop(Role::Info, &ident);
 
-    let linux_section = linker::linux::section(&ident);
-    let linux_section_start = linker::linux::section_start(&ident);
-    let linux_section_stop = linker::linux::section_stop(&ident);
-    let linux_dupcheck = linux_section.replacen("linkme", "linkm2", 1);
-    let linux_dupcheck_start = linux_section_start.replacen("linkme", "linkm2", 1);
-    let linux_dupcheck_stop = linux_section_stop.replacen("linkme", "linkm2", 1);
-
-    let macho_section = linker::macho::section(&ident);
-    let macho_section_start = linker::macho::section_start(&ident);
-    let macho_section_stop = linker::macho::section_stop(&ident);
-    let macho_dupcheck = macho_section.replacen("linkme", "linkm2", 1);
-    let macho_dupcheck_start = macho_section_start.replacen("linkme", "linkm2", 1);
-    let macho_dupcheck_stop = macho_section_stop.replacen("linkme", "linkm2", 1);
-
-    let windows_section = linker::windows::section(&ident);
-    let windows_section_start = linker::windows::section_start(&ident);
-    let windows_section_stop = linker::windows::section_stop(&ident);
-    let windows_dupcheck = windows_section.replacen("linkme", "linkm2", 1);
-    let windows_dupcheck_start = windows_section_start.replacen("linkme", "linkm2", 1);
-    let windows_dupcheck_stop = windows_section_stop.replacen("linkme", "linkm2", 1);
-
-    let illumos_section = linker::illumos::section(&ident);
-    let illumos_section_start = linker::illumos::section_start(&ident);
-    let illumos_section_stop = linker::illumos::section_stop(&ident);
-    let illumos_dupcheck = illumos_section.replacen("linkme", "linkm2", 1);
-    let illumos_dupcheck_start = illumos_section_start.replacen("linkme", "linkm2", 1);
-    let illumos_dupcheck_stop = illumos_section_stop.replacen("linkme", "linkm2", 1);
-
-    let bsd_section = linker::bsd::section(&ident);
-    let bsd_section_start = linker::bsd::section_start(&ident);
-    let bsd_section_stop = linker::bsd::section_stop(&ident);
-    let bsd_dupcheck = bsd_section.replacen("linkme", "linkm2", 1);
-    let bsd_dupcheck_start = bsd_section_start.replacen("linkme", "linkm2", 1);
-    let bsd_dupcheck_stop = bsd_section_stop.replacen("linkme", "linkm2", 1);
+    let mut windows_section = linker::windows::section(Role::Elements, &ident);
+    let mut windows_section_start = linker::windows::section_start(Role::Elements, &ident);
+    let mut windows_section_stop = linker::windows::section_stop(Role::Elements, &ident);
+    let windows_dupcheck = linker::windows::section(Role::Dupcheck, &ident);
+    let windows_dupcheck_start = linker::windows::section_start(Role::Dupcheck, &ident);
+    let windows_dupcheck_stop = linker::windows::section_stop(Role::Dupcheck, &ident);
+    let windows_info = linker::windows::section(Role::Info, &ident);
+    let windows_info_start = linker::windows::section_start(Role::Info, &ident);
+    let windows_info_stop = linker::windows::section_stop(Role::Info, &ident);
+
+    let mut illumos_section = linker::illumos::section(Role::Elements, &ident);
+    let mut illumos_section_start = linker::illumos::section_start(Role::Elements, &ident);
+    let mut illumos_section_stop = linker::illumos::section_stop(Role::Elements, &ident);
+    let illumos_dupcheck = linker::illumos::section(Role::Dupcheck, &ident);
+    let illumos_dupcheck_start = linker::illumos::section_start(Role::Dupcheck, &ident);
+    let illumos_dupcheck_stop = linker::illumos::section_stop(Role::Dupcheck, &ident);
+    let illumos_info = linker::illumos::section(Role::Info, &ident);
+    let illumos_info_start = linker::illumos::section_start(Role::Info, &ident);
+    let illumos_info_stop = linker::illumos::section_stop(Role::Info, &ident);
+
+    let mut bsd_section = linker::bsd::section(Role::Elements, &ident);
+    let mut bsd_section_start = linker::bsd::section_start(Role::Elements, &ident);
+    let mut bsd_section_stop = linker::bsd::section_stop(Role::Elements, &ident);
+    let bsd_dupcheck = linker::bsd::section(Role::Dupcheck, &ident);
+    let bsd_dupcheck_start = linker::bsd::section_start(Role::Dupcheck, &ident);
+    let bsd_dupcheck_stop = linker::bsd::section_stop(Role::Dupcheck, &ident);
+    let bsd_info = linker::bsd::section(Role::Info, &ident);
+    let bsd_info_start = linker::bsd::section_start(Role::Info, &ident);
+    let bsd_info_stop = linker::bsd::section_stop(Role::Info, &ident);
+
+    // A section name given by #[linkme(section = "...")] or extern_section
+    // replaces only the section holding the elements. The dupcheck and info
+    // sections keep their names derived from the identifier.
+    let mut invalid_section = TokenStream::new();
+    if let Some(section) = section {
+        let name = section.value();
+        invalid_section = validate_section(section);
+
+        linux_section.clone_from(&name);
+        linux_section_start = format!("__start_{}", name);
+        linux_section_stop = format!("__stop_{}", name);
+
+        macho_section = format!("__DATA,{},regular,no_dead_strip", name);
+        macho_section_start = format!("\x01section$start$__DATA${}", name);
+        macho_section_stop = format!("\x01section$end$__DATA${}", name);
+
+        windows_section = format!("{}$b", name);
+        windows_section_start = format!("{}$a", name);
+        windows_section_stop = format!("{}$c", name);
+
+        illumos_section.clone_from(&name);
+        illumos_section_start = format!("__start_{}", name);
+        illumos_section_stop = format!("__stop_{}", name);
+
+        bsd_section.clone_from(&name);
+        bsd_section_start = format!("__start_{}", name);
+        bsd_section_stop = format!("__stop_{}", name);
+    }
 
     let call_site = Span::call_site();
     let link_section_macro_str = format!("_linkme_macro_{}", ident);
     let link_section_macro = Ident::new(&link_section_macro_str, call_site);
 
-    let unsafe_extern = if cfg!(no_unsafe_extern_blocks) {
-        None
-    } else {
-        Some(Token![unsafe](call_site))
-    };
+    let unsafe_extern = unsafe_extern();
+    let (unsafe_attr, link_section_attr, export_name_attr) = unsafe_attrs();
 
-    let (unsafe_attr, link_section_attr) = if cfg!(no_unsafe_attributes) {
-        // #[cfg_attr(all(), link_section = ...)]
-        (
-            Ident::new("cfg_attr", call_site),
-            quote!(all(), link_section),
-        )
+    // Two declarations of the same slice each define this strong symbol, which
+    // the linker rejects as a multiple definition naming the slice, instead of
+    // static_slice() panicking at runtime.
+    let link_dupcheck = if cfg!(feature = "link_dupcheck") {
+        let symbol = linker::symbol::duplicate(&ident);
+        Some(quote! {
+            #used
+            #[#unsafe_attr(#export_name_attr = #symbol)]
+            static LINKME_DUPLICATE: #linkme_path::#private::isize = 0;
+        })
     } else {
-        // #[unsafe(link_section = ...)]
-        (Ident::new("unsafe", call_site), quote!(link_section))
+        None
     };
 
-    quote! {
-        #(#attrs)*
-        #vis static #ident: #linkme_path::DistributedSlice<#ty> = {
+    // A slice over an extern section has neither the dupcheck section nor a
+    // LINKME_PLEASE to guarantee that the section exists: the section is
+    // expected to be populated by code that linkme did not compile, and the
+    // foreign table may legitimately be viewed by more than one declaration.
+    let (dupcheck, linkme_please, dupcheck_start, dupcheck_stop) = if extern_section.is_none() {
+        let dupcheck = quote! {
             #[cfg(any(
                 target_os = "none",
                 target_os = "linux",
@@ -147,18 +415,6 @@ pub fn expand(input: TokenStream) -> TokenStream {
                 target_os = "psp",
             ))]
             #unsafe_extern extern "Rust" {
-                #[cfg_attr(any(target_os = "none", target_os = "linux", target_os = "android", target_os = "fuchsia", target_os = "psp"), link_name = #linux_section_start)]
-                #[cfg_attr(any(target_os = "macos", target_os = "ios", target_os = "tvos"), link_name = #macho_section_start)]
-                #[cfg_attr(target_os = "illumos", link_name = #illumos_section_start)]
-                #[cfg_attr(any(target_os = "freebsd", target_os = "openbsd"), link_name = #bsd_section_start)]
-                static LINKME_START: [<#ty as #linkme_path::#private::Slice>::Element; 0];
-
-                #[cfg_attr(any(target_os = "none", target_os = "linux", target_os = "android", target_os = "fuchsia", target_os = "psp"), link_name = #linux_section_stop)]
-                #[cfg_attr(any(target_os = "macos", target_os = "ios", target_os = "tvos"), link_name = #macho_section_stop)]
-                #[cfg_attr(target_os = "illumos", link_name = #illumos_section_stop)]
-                #[cfg_attr(any(target_os = "freebsd", target_os = "openbsd"), link_name = #bsd_section_stop)]
-                static LINKME_STOP: [<#ty as #linkme_path::#private::Slice>::Element; 0];
-
                 #[cfg_attr(any(target_os = "none", target_os = "linux", target_os = "android", target_os = "fuchsia", target_os = "psp"), link_name = #linux_dupcheck_start)]
                 #[cfg_attr(any(target_os = "macos", target_os = "ios", target_os = "tvos"), link_name = #macho_dupcheck_start)]
                 #[cfg_attr(target_os = "illumos", link_name = #illumos_dupcheck_start)]
@@ -172,18 +428,6 @@ pub fn expand(input: TokenStream) -> TokenStream {
                 static DUPCHECK_STOP: ();
             }
 
-            // On Windows/UEFI, use non-ZST boundary elements because some
-            // codegen backends emit non-zero bytes for ZST statics in custom
-            // PE/COFF sections, which corrupts section boundary and dupcheck
-            // arithmetic.
-            #[cfg(any(target_os = "uefi", target_os = "windows"))]
-            #[#unsafe_attr(#link_section_attr = #windows_section_start)]
-            static LINKME_START: #linkme_path::#private::BoundaryElement<#ty> = #linkme_path::#private::BoundaryElement::<#ty>::uninit();
-
-            #[cfg(any(target_os = "uefi", target_os = "windows"))]
-            #[#unsafe_attr(#link_section_attr = #windows_section_stop)]
-            static LINKME_STOP: #linkme_path::#private::BoundaryElement<#ty> = #linkme_path::#private::BoundaryElement::<#ty>::uninit();
-
             #[cfg(any(target_os = "uefi", target_os = "windows"))]
             #[#unsafe_attr(#link_section_attr = #windows_dupcheck_start)]
             static DUPCHECK_START: #linkme_path::#private::isize = 0;
@@ -192,6 +436,17 @@ pub fn expand(input: TokenStream) -> TokenStream {
             #[#unsafe_attr(#link_section_attr = #windows_dupcheck_stop)]
             static DUPCHECK_STOP: #linkme_path::#private::isize = 0;
 
+            #used
+            #[cfg_attr(any(target_os = "none", target_os = "linux", target_os = "android", target_os = "fuchsia", target_os = "psp"), #unsafe_attr(#link_section_attr = #linux_dupcheck))]
+            #[cfg_attr(any(target_os = "macos", target_os = "ios", target_os = "tvos"), #unsafe_attr(#link_section_attr = #macho_dupcheck))]
+            #[cfg_attr(any(target_os = "uefi", target_os = "windows"), #unsafe_attr(#link_section_attr = #windows_dupcheck))]
+            #[cfg_attr(target_os = "illumos", #unsafe_attr(#link_section_attr = #illumos_dupcheck))]
+            #[cfg_attr(any(target_os = "freebsd", target_os = "openbsd"), #unsafe_attr(#link_section_attr = #bsd_dupcheck))]
+            static DUPCHECK: #linkme_path::#private::isize = 1;
+
+            #link_dupcheck
+        };
+        let linkme_please = quote! {
             #used
             #[cfg(any(
                 target_os = "none",
@@ -207,66 +462,545 @@ pub fn expand(input: TokenStream) -> TokenStream {
             #[cfg_attr(target_os = "illumos", #unsafe_attr(#link_section_attr = #illumos_section))]
             #[cfg_attr(any(target_os = "freebsd", target_os = "openbsd"), #unsafe_attr(#link_section_attr = #bsd_section))]
             static mut LINKME_PLEASE: [<#ty as #linkme_path::#private::Slice>::Element; 0] = [];
+        };
+        let dupcheck_start = quote! {
+            #linkme_path::#private::ptr::addr_of!(DUPCHECK_START)
+                .cast::<#linkme_path::#private::isize>()
+        };
+        let dupcheck_stop = quote! {
+            #linkme_path::#private::ptr::addr_of!(DUPCHECK_STOP)
+                .cast::<#linkme_path::#private::isize>()
+        };
+        (dupcheck, linkme_please, dupcheck_start, dupcheck_stop)
+    } else {
+        let null = quote!(#linkme_path::#private::ptr::null());
+        (TokenStream::new(), TokenStream::new(), null.clone(), null)
+    };
+
+    // Each element of a slice declared with #[linkme(element_info)] is paired
+    // with an ElementInfo in a separate section. The ElementInfo points back to
+    // its element, so the two sections need not be laid out in the same order.
+    // Checking for crates named in force_link! relies on the module path of
+    // each element, so force_link implies element_info.
+    let element_info = options.element_info || options.force_link;
+    let (element_info_decl, element_info_ctor, element_info_item) = if element_info {
+        let decl = quote! {
+            #[cfg(any(
+                target_os = "none",
+                target_os = "linux",
+                target_os = "macos",
+                target_os = "ios",
+                target_os = "tvos",
+                target_os = "android",
+                target_os = "fuchsia",
+                target_os = "illumos",
+                target_os = "freebsd",
+                target_os = "openbsd",
+                target_os = "psp",
+            ))]
+            #unsafe_extern extern "Rust" {
+                #[cfg_attr(any(target_os = "none", target_os = "linux", target_os = "android", target_os = "fuchsia", target_os = "psp"), link_name = #linux_info_start)]
+                #[cfg_attr(any(target_os = "macos", target_os = "ios", target_os = "tvos"), link_name = #macho_info_start)]
+                #[cfg_attr(target_os = "illumos", link_name = #illumos_info_start)]
+                #[cfg_attr(any(target_os = "freebsd", target_os = "openbsd"), link_name = #bsd_info_start)]
+                static INFO_START: [#linkme_path::ElementInfo; 0];
+
+                #[cfg_attr(any(target_os = "none", target_os = "linux", target_os = "android", target_os = "fuchsia", target_os = "psp"), link_name = #linux_info_stop)]
+                #[cfg_attr(any(target_os = "macos", target_os = "ios", target_os = "tvos"), link_name = #macho_info_stop)]
+                #[cfg_attr(target_os = "illumos", link_name = #illumos_info_stop)]
+                #[cfg_attr(any(target_os = "freebsd", target_os = "openbsd"), link_name = #bsd_info_stop)]
+                static INFO_STOP: [#linkme_path::ElementInfo; 0];
+            }
+
+            #[cfg(any(target_os = "uefi", target_os = "windows"))]
+            #[#unsafe_attr(#link_section_attr = #windows_info_start)]
+            static INFO_START: #linkme_path::#private::BoundaryElement<[#linkme_path::ElementInfo]> = #linkme_path::#private::BoundaryElement::<[#linkme_path::ElementInfo]>::uninit();
+
+            #[cfg(any(target_os = "uefi", target_os = "windows"))]
+            #[#unsafe_attr(#link_section_attr = #windows_info_stop)]
+            static INFO_STOP: #linkme_path::#private::BoundaryElement<[#linkme_path::ElementInfo]> = #linkme_path::#private::BoundaryElement::<[#linkme_path::ElementInfo]>::uninit();
 
             #used
-            #[cfg_attr(any(target_os = "none", target_os = "linux", target_os = "android", target_os = "fuchsia", target_os = "psp"), #unsafe_attr(#link_section_attr = #linux_dupcheck))]
-            #[cfg_attr(any(target_os = "macos", target_os = "ios", target_os = "tvos"), #unsafe_attr(#link_section_attr = #macho_dupcheck))]
-            #[cfg_attr(any(target_os = "uefi", target_os = "windows"), #unsafe_attr(#link_section_attr = #windows_dupcheck))]
-            #[cfg_attr(target_os = "illumos", #unsafe_attr(#link_section_attr = #illumos_dupcheck))]
-            #[cfg_attr(any(target_os = "freebsd", target_os = "openbsd"), #unsafe_attr(#link_section_attr = #bsd_dupcheck))]
-            static DUPCHECK: #linkme_path::#private::isize = 1;
+            #[cfg(any(
+                target_os = "none",
+                target_os = "linux",
+                target_os = "android",
+                target_os = "fuchsia",
+                target_os = "illumos",
+                target_os = "freebsd",
+                target_os = "openbsd",
+                target_os = "psp",
+            ))]
+            #[cfg_attr(any(target_os = "none", target_os = "linux", target_os = "android", target_os = "fuchsia", target_os = "psp"), #unsafe_attr(#link_section_attr = #linux_info))]
+            #[cfg_attr(target_os = "illumos", #unsafe_attr(#link_section_attr = #illumos_info))]
+            #[cfg_attr(any(target_os = "freebsd", target_os = "openbsd"), #unsafe_attr(#link_section_attr = #bsd_info))]
+            static mut INFO_PLEASE: [#linkme_path::ElementInfo; 0] = [];
+        };
+        let ctor = quote! {
+            .private_element_info(
+                #linkme_path::#private::ptr::addr_of!(INFO_START)
+                    .cast::<#linkme_path::ElementInfo>(),
+                #linkme_path::#private::ptr::addr_of!(INFO_STOP)
+                    .cast::<#linkme_path::ElementInfo>(),
+            )
+        };
+        let item = quote! {
+            $(const _: () = {
+                #used
+                #[cfg_attr(any(target_os = "none", target_os = "linux", target_os = "android", target_os = "fuchsia", target_os = "psp"), #unsafe_attr(#link_section_attr = #linux_info))]
+                #[cfg_attr(any(target_os = "macos", target_os = "ios", target_os = "tvos"), #unsafe_attr(#link_section_attr = #macho_info))]
+                #[cfg_attr(any(target_os = "uefi", target_os = "windows"), #unsafe_attr(#link_section_attr = #windows_info))]
+                #[cfg_attr(target_os = "illumos", #unsafe_attr(#link_section_attr = #illumos_info))]
+                #[cfg_attr(any(target_os = "freebsd", target_os = "openbsd"), #unsafe_attr(#link_section_attr = #bsd_info))]
+                static LINKME_INFO: #linkme_path::ElementInfo = $info;
+            };)?
+        };
+        (Some(decl), Some(ctor), Some(item))
+    } else {
+        (None, None, None)
+    };
 
-            #[cfg(not(any(
+    let backend = Backend::get();
+    let fallback_cfg = backend.fallback_cfg();
+
+    // The number of elements is checked at the first access to the slice. A
+    // slice requiring at least one element is also checked at link time on ELF
+    // targets: each element defines a weak symbol in global_asm, guarded so
+    // that elements sharing an object file define it once, and the
+    // declaration refers to it.
+    let cardinality_ctor = if cardinality.min > 0 || cardinality.max.is_some() {
+        let min = cardinality.min;
+        let max = if let Some(max) = cardinality.max {
+            quote!(#max)
+        } else {
+            quote!(#linkme_path::#private::usize::MAX)
+        };
+        Some(quote!(.private_cardinality(#min, #max)))
+    } else {
+        None
+    };
+    // Zero-sized elements are counted through markers in the section, which
+    // are only relied on where the linker lays out each input section without
+    // padding, as on ELF and Mach-O. A PE/COFF linker is free to pad the
+    // grouped sections, so zero-sized elements are rejected there. A marker is
+    // zero bytes long for any other element type.
+    //
+    // Whether an element may be zero-sized is decided once here from the
+    // syntax of the element type, so that the elements of a slice of
+    // functions, references, or integers carry no marker.
+    let may_be_zero_sized = match &ty {
+        Type::Slice(ty) => kind != Kind::Map && !ty::is_nonzero_sized(&ty.elem),
+        _ => false,
+    };
+    let (zst_marker_cfg, zst_unsupported) = match backend {
+        Backend::Both => {
+            let msg = "zero-sized distributed slice elements are not supported on Windows and UEFI";
+            (
+                Some(quote!(#[cfg(not(any(target_os = "uefi", target_os = "windows")))])),
+                Some(quote! {
+                    #[cfg(any(target_os = "uefi", target_os = "windows"))]
+                    const _: () = ::core::assert!($len == 0, #msg);
+                }),
+            )
+        }
+        Backend::Fallback => (None, None),
+    };
+    // Crates expected by force_link! to contribute to the slice that
+    // contributed no element are reported at the first access, once. The
+    // expectations are the elements of a second slice owned by the
+    // declaration, so that only a slice with #[linkme(force_link)] refers to
+    // any section holding them.
+    let force_link_sections = if options.force_link {
+        Some([
+            linker::linux::section(Role::ForceLink, &ident),
+            linker::macho::section(Role::ForceLink, &ident),
+            linker::windows::section(Role::ForceLink, &ident),
+            linker::illumos::section(Role::ForceLink, &ident),
+            linker::bsd::section(Role::ForceLink, &ident),
+        ])
+    } else {
+        None
+    };
+    let (force_link_item, force_link_ctor) = if let Some(sections) = &force_link_sections {
+        let [linux_section, _, _, illumos_section, bsd_section] = sections;
+        let linux_start = linker::linux::section_start(Role::ForceLink, &ident);
+        let linux_stop = linker::linux::section_stop(Role::ForceLink, &ident);
+        let macho_start = linker::macho::section_start(Role::ForceLink, &ident);
+        let macho_stop = linker::macho::section_stop(Role::ForceLink, &ident);
+        let windows_start = linker::windows::section_start(Role::ForceLink, &ident);
+        let windows_stop = linker::windows::section_stop(Role::ForceLink, &ident);
+        let illumos_start = linker::illumos::section_start(Role::ForceLink, &ident);
+        let illumos_stop = linker::illumos::section_stop(Role::ForceLink, &ident);
+        let bsd_start = linker::bsd::section_start(Role::ForceLink, &ident);
+        let bsd_stop = linker::bsd::section_stop(Role::ForceLink, &ident);
+
+        let native = quote! {
+            #[cfg(any(
                 target_os = "none",
                 target_os = "linux",
                 target_os = "macos",
                 target_os = "ios",
                 target_os = "tvos",
-                target_os = "windows",
-                target_os = "uefi",
                 target_os = "android",
                 target_os = "fuchsia",
                 target_os = "illumos",
                 target_os = "freebsd",
                 target_os = "openbsd",
                 target_os = "psp",
-            )))]
-            #unsupported_platform
+            ))]
+            #unsafe_extern extern "Rust" {
+                #[cfg_attr(any(target_os = "none", target_os = "linux", target_os = "android", target_os = "fuchsia", target_os = "psp"), link_name = #linux_start)]
+                #[cfg_attr(any(target_os = "macos", target_os = "ios", target_os = "tvos"), link_name = #macho_start)]
+                #[cfg_attr(target_os = "illumos", link_name = #illumos_start)]
+                #[cfg_attr(any(target_os = "freebsd", target_os = "openbsd"), link_name = #bsd_start)]
+                static FORCE_LINKED_START: [#linkme_path::#private::ForceLinked; 0];
+
+                #[cfg_attr(any(target_os = "none", target_os = "linux", target_os = "android", target_os = "fuchsia", target_os = "psp"), link_name = #linux_stop)]
+                #[cfg_attr(any(target_os = "macos", target_os = "ios", target_os = "tvos"), link_name = #macho_stop)]
+                #[cfg_attr(target_os = "illumos", link_name = #illumos_stop)]
+                #[cfg_attr(any(target_os = "freebsd", target_os = "openbsd"), link_name = #bsd_stop)]
+                static FORCE_LINKED_STOP: [#linkme_path::#private::ForceLinked; 0];
+            }
+
+            #[cfg(any(target_os = "uefi", target_os = "windows"))]
+            #[#unsafe_attr(#link_section_attr = #windows_start)]
+            static FORCE_LINKED_START: #linkme_path::#private::BoundaryElement<[#linkme_path::#private::ForceLinked]> = #linkme_path::#private::BoundaryElement::<[#linkme_path::#private::ForceLinked]>::uninit();
 
+            #[cfg(any(target_os = "uefi", target_os = "windows"))]
+            #[#unsafe_attr(#link_section_attr = #windows_stop)]
+            static FORCE_LINKED_STOP: #linkme_path::#private::BoundaryElement<[#linkme_path::#private::ForceLinked]> = #linkme_path::#private::BoundaryElement::<[#linkme_path::#private::ForceLinked]>::uninit();
+
+            #used
+            #[cfg(any(
+                target_os = "none",
+                target_os = "linux",
+                target_os = "android",
+                target_os = "fuchsia",
+                target_os = "illumos",
+                target_os = "freebsd",
+                target_os = "openbsd",
+                target_os = "psp",
+            ))]
+            #[cfg_attr(any(target_os = "none", target_os = "linux", target_os = "android", target_os = "fuchsia", target_os = "psp"), #unsafe_attr(#link_section_attr = #linux_section))]
+            #[cfg_attr(target_os = "illumos", #unsafe_attr(#link_section_attr = #illumos_section))]
+            #[cfg_attr(any(target_os = "freebsd", target_os = "openbsd"), #unsafe_attr(#link_section_attr = #bsd_section))]
+            static mut FORCE_LINKED_PLEASE: [#linkme_path::#private::ForceLinked; 0] = [];
+        };
+        let native_slice = quote! {
             unsafe {
                 #linkme_path::DistributedSlice::private_new(
                     #name,
-                    #linkme_path::#private::ptr::addr_of!(LINKME_START)
-                        .cast::<<#ty as #linkme_path::#private::Slice>::Element>(),
-                    #linkme_path::#private::ptr::addr_of!(LINKME_STOP)
-                        .cast::<<#ty as #linkme_path::#private::Slice>::Element>(),
-                    #linkme_path::#private::ptr::addr_of!(DUPCHECK_START)
-                        .cast::<#linkme_path::#private::isize>(),
-                    #linkme_path::#private::ptr::addr_of!(DUPCHECK_STOP)
-                        .cast::<#linkme_path::#private::isize>(),
+                    #linkme_path::#private::ptr::addr_of!(FORCE_LINKED_START)
+                        .cast::<#linkme_path::#private::ForceLinked>(),
+                    #linkme_path::#private::ptr::addr_of!(FORCE_LINKED_STOP)
+                        .cast::<#linkme_path::#private::ForceLinked>(),
+                    #linkme_path::#private::ptr::null(),
+                    #linkme_path::#private::ptr::null(),
                 )
             }
         };
+        let fallback_slice = quote! {{
+            static LINKME_REGISTRY: #linkme_path::#private::Registry =
+                #linkme_path::#private::Registry::new();
 
-        #[doc(hidden)]
-        #[macro_export]
-        macro_rules! #link_section_macro {
+            #linkme_path::DistributedSlice::private_registry(#name, &LINKME_REGISTRY, false)
+        }};
+        let (native, slice) = match backend {
+            Backend::Both => (
+                Some(native),
+                quote! {{
+                    #[cfg(not(#fallback_cfg))]
+                    let slice = #native_slice;
+                    #[cfg(#fallback_cfg)]
+                    let slice = #fallback_slice;
+                    slice
+                }},
+            ),
+            Backend::Fallback => (None, fallback_slice),
+        };
+        let item = quote! {
+            static LINKME_FORCE_LINK: #linkme_path::#private::AtomicBool =
+                #linkme_path::#private::AtomicBool::new(false);
+
+            #native
+
+            static LINKME_FORCE_LINKED: #linkme_path::DistributedSlice<[#linkme_path::#private::ForceLinked]> = #slice;
+        };
+        let ctor = quote! {
+            .private_force_link(&LINKME_FORCE_LINK, &LINKME_FORCE_LINKED)
+        };
+        (Some(item), Some(ctor))
+    } else {
+        (None, None)
+    };
+
+    let nonempty_cfg = quote! {
+        all(
+            any(
+                target_os = "none",
+                target_os = "linux",
+                target_os = "android",
+                target_os = "fuchsia",
+                target_os = "illumos",
+                target_os = "freebsd",
+                target_os = "openbsd",
+            ),
+            any(
+                target_arch = "x86",
+                target_arch = "x86_64",
+                target_arch = "arm",
+                target_arch = "aarch64",
+                target_arch = "riscv32",
+                target_arch = "riscv64",
+            ),
+        )
+    };
+    let (nonempty_decl, nonempty_ctor, nonempty_item) = if cardinality.min > 0
+        && kind == Kind::Slice
+        && extern_section.is_none()
+    {
+        let symbol = linker::symbol::nonempty(&ident);
+        let decl = quote! {
+            #[cfg(#nonempty_cfg)]
+            #unsafe_extern extern "Rust" {
+                #[link_name = #symbol]
+                static LINKME_NONEMPTY: u8;
+            }
+        };
+        let ctor = quote! {
+            #[cfg(#nonempty_cfg)]
+            let slice = slice.private_nonempty(#linkme_path::#private::ptr::addr_of!(LINKME_NONEMPTY));
+        };
+        let section = format!(".pushsection .rodata.{},\"a\"", symbol);
+        let ifndef = format!(".ifndef {}", symbol);
+        let weak = format!(".weak {}", symbol);
+        let label = format!("{}:", symbol);
+        let item = quote! {
+            #[cfg(all(#nonempty_cfg, not(#fallback_cfg)))]
+            const _: () = {
+                mod linkme_nonempty {
+                    ::core::arch::global_asm!(
+                        #ifndef,
+                        #section,
+                        #weak,
+                        #label,
+                        ".byte 0",
+                        ".popsection",
+                        ".endif",
+                    );
+                }
+            };
+        };
+        (Some(decl), Some(ctor), Some(item))
+    } else {
+        (None, None, None)
+    };
+
+    // A slice of at most one element is also checked at link time, on every
+    // platform: each element is exported under the same symbol, so a second
+    // element is rejected as a multiple definition naming the slice.
+    let too_many_attr = if cardinality.max == Some(1) && kind == Kind::Slice {
+        let symbol = linker::symbol::too_many(&ident);
+        Some(quote!(#[#unsafe_attr(#export_name_attr = #symbol)]))
+    } else {
+        None
+    };
+
+    // A shared object whose version script hides the __start_ and __stop_
+    // symbols, as rustc's does for a cdylib under GNU ld and gold, still
+    // exports the bounds of the section for linkme::dynamic under this symbol.
+    let bounds_item = if cfg!(feature = "dynamic") && kind == Kind::Slice {
+        let symbol = linker::symbol::bounds(&ident);
+        Some(quote! {
+            #[cfg(target_os = "linux")]
+            #[#unsafe_attr(#export_name_attr = #symbol)]
+            static LINKME_BOUNDS: #linkme_path::#private::SectionBounds = unsafe {
+                #linkme_path::#private::SectionBounds::new(
+                    #linkme_path::#private::ptr::addr_of!(LINKME_START).cast::<u8>(),
+                    #linkme_path::#private::ptr::addr_of!(LINKME_STOP).cast::<u8>(),
+                )
+            };
+        })
+    } else {
+        None
+    };
+    let bounds_symbol = linker::symbol::bounds(&ident);
+
+    let native_slice = quote! {
+        unsafe {
+            let slice = #linkme_path::DistributedSlice::private_new(
+                #name,
+                #linkme_path::#private::ptr::addr_of!(LINKME_START)
+                    .cast::<<#ty as #linkme_path::#private::Slice>::Element>(),
+                #linkme_path::#private::ptr::addr_of!(LINKME_STOP)
+                    .cast::<<#ty as #linkme_path::#private::Slice>::Element>(),
+                #dupcheck_start,
+                #dupcheck_stop,
+            )
+            .private_elf_section(#linux_section, #bounds_symbol)
+            #element_info_ctor
+            #cardinality_ctor
+            #force_link_ctor;
+            #nonempty_ctor
+            slice
+        }
+    };
+
+    // WebAssembly, and with the fallback feature any other target without
+    // linker support, registers elements at runtime into a registry owned by
+    // the declaration.
+    let fallback_slice = quote! {
+        #linkme_path::DistributedSlice::private_registry(#name, &LINKME_REGISTRY, #element_info)
+            #cardinality_ctor
+            #force_link_ctor
+    };
+    let fallback_error = extern_section.as_ref().map(|extern_section| {
+        let msg = "extern_section is not supported by the fallback backend";
+        let error = Error::new(extern_section.span(), msg).to_compile_error();
+        quote! {
+            #[cfg(#fallback_cfg)]
+            #error
+        }
+    });
+    let slice = match backend {
+        Backend::Both => quote! {{
+            #[cfg(not(#fallback_cfg))]
+            let slice = #native_slice;
+            #[cfg(#fallback_cfg)]
+            let slice = #fallback_slice;
+            slice
+        }},
+        Backend::Fallback => fallback_slice,
+    };
+
+    let init = match kind {
+        Kind::Slice => slice,
+        Kind::Map => quote! {
+            static INDEX: #linkme_path::#private::MapIndex =
+                #linkme_path::#private::MapIndex::new();
+
+            #linkme_path::DistributedMap::private_new(#slice, &INDEX)
+        },
+        Kind::Slot => quote! {
+            static DEFAULT: <#ty as #linkme_path::#private::Slice>::Element = #default;
+
+            unsafe {
+                #linkme_path::DistributedSlot::private_with_default(
+                    #name,
+                    #linkme_path::#private::ptr::addr_of!(DEFAULT),
+                    #slice,
+                )
+            }
+        },
+    };
+
+    // Registers an element with the fallback backend from a constructor. The
+    // element is reached through its ElementInfo, and the slice by the path
+    // that the element passed as the macro's own.
+    let macro_linkme_path = macro_linkme_path(&linkme_path);
+    let fallback_register = |info: TokenStream, slice: TokenStream| {
+        quote! {
+            #[cfg(#fallback_cfg)]
+            const _: () = {
+                static LINKME_NODE: #macro_linkme_path::#private::Node =
+                    #macro_linkme_path::#private::Node::new(#info);
+
+                #used
+                #[cfg_attr(not(any(target_os = "macos", target_os = "ios", target_os = "tvos", target_os = "uefi", target_os = "windows")), #unsafe_attr(#link_section_attr = ".init_array"))]
+                #[cfg_attr(any(target_os = "macos", target_os = "ios", target_os = "tvos"), #unsafe_attr(#link_section_attr = "__DATA,__mod_init_func"))]
+                #[cfg_attr(any(target_os = "uefi", target_os = "windows"), #unsafe_attr(#link_section_attr = ".CRT$XCU"))]
+                static LINKME_CONSTRUCTOR: extern "C" fn() = {
+                    extern "C" fn constructor() {
+                        #slice.private_register(&LINKME_NODE);
+                    }
+                    constructor
+                };
+            };
+        }
+    };
+    let register = fallback_register(quote!($info), quote!($macro));
+
+    // force_link!(krate => SLICE) records its expectation through the slice's
+    // own macro, so that it is attached to this declaration however the path
+    // to it is spelled.
+    let force_link_arm = if let Some(sections) = &force_link_sections {
+        let [linux_section, macho_section, windows_section, illumos_section, bsd_section] =
+            sections;
+        let section_attrs = match backend {
+            Backend::Both => Some(quote! {
+                #[cfg_attr(any(target_os = "none", target_os = "linux", target_os = "android", target_os = "fuchsia", target_os = "psp"), #unsafe_attr(#link_section_attr = #linux_section))]
+                #[cfg_attr(any(target_os = "macos", target_os = "ios", target_os = "tvos"), #unsafe_attr(#link_section_attr = #macho_section))]
+                #[cfg_attr(any(target_os = "uefi", target_os = "windows"), #unsafe_attr(#link_section_attr = #windows_section))]
+                #[cfg_attr(target_os = "illumos", #unsafe_attr(#link_section_attr = #illumos_section))]
+                #[cfg_attr(any(target_os = "freebsd", target_os = "openbsd"), #unsafe_attr(#link_section_attr = #bsd_section))]
+            }),
+            Backend::Fallback => None,
+        };
+        let info = quote! {
+            #macro_linkme_path::ElementInfo::private_new(
+                #macro_linkme_path::#private::ptr::addr_of!(LINKME_FORCE_LINKED).cast::<()>(),
+                $krate,
+                #macro_linkme_path::#private::module_path!(),
+                #macro_linkme_path::#private::file!(),
+                #macro_linkme_path::#private::line!(),
+                #macro_linkme_path::#private::Option::None,
+            )
+        };
+        let register = fallback_register(info, quote!($macro.private_force_linked()));
+        quote! {
+            (
+                #![linkme_macro = $macro:path]
+                #![linkme_force_link = $krate:literal]
+            ) => {
+                const _: () = {
+                    #used
+                    #section_attrs
+                    static LINKME_FORCE_LINKED: #macro_linkme_path::#private::ForceLinked =
+                        #macro_linkme_path::#private::ForceLinked::private_new($krate);
+
+                    #register
+                };
+            };
+        }
+    } else {
+        force_link_error_arm(&name)
+    };
+
+    let zst_marker = may_be_zero_sized.then(|| {
+        let len = quote!(#macro_linkme_path::#private::zst_marker_len::<$element>());
+        quote!(#![linkme_zst_marker = #len])
+    });
+
+    // Only the PE/COFF linker sorts grouped sections by the suffix after `$`,
+    // so a sort key extends only the Windows section name. Elsewhere, a
+    // positioned element is rejected unless the fallback backend sorts it.
+    let entry_arms = match kind {
+        Kind::Slice | Kind::Map => quote! {
             (
                 #![linkme_macro = $macro:path]
                 #![linkme_sort_key = $key:tt]
+                #![linkme_element = $element:ty]
+                #![linkme_info = $info:expr]
                 $item:item
             ) => {
                 $macro ! {
-                    #![linkme_linux_section = concat!(#linux_section, $key)]
-                    #![linkme_macho_section = concat!(#macho_section, $key)]
+                    #![linkme_linux_section = #linux_section]
+                    #![linkme_macho_section = #macho_section]
                     #![linkme_windows_section = concat!(#windows_section, $key)]
-                    #![linkme_illumos_section = concat!(#illumos_section, $key)]
-                    #![linkme_bsd_section = concat!(#bsd_section, $key)]
+                    #![linkme_illumos_section = #illumos_section]
+                    #![linkme_bsd_section = #bsd_section]
+                    #too_many_attr
                     $item
+                    #![linkme_info = $info]
+                    #zst_marker
                 }
+                #register
+                #nonempty_item
             };
             (
                 #![linkme_macro = $macro:path]
+                #![linkme_element = $element:ty]
+                #![linkme_info = $info:expr]
                 $item:item
             ) => {
                 $macro ! {
@@ -275,9 +1009,174 @@ pub fn expand(input: TokenStream) -> TokenStream {
                     #![linkme_windows_section = #windows_section]
                     #![linkme_illumos_section = #illumos_section]
                     #![linkme_bsd_section = #bsd_section]
+                    #too_many_attr
                     $item
+                    #![linkme_info = $info]
+                    #zst_marker
                 }
+                #register
+                #nonempty_item
+            };
+        },
+        Kind::Slot => {
+            let msg = format!(
+                "#[distributed_slot] {} has a default value; use #[distributed_slot({}, override)]",
+                name, name,
+            );
+            quote! {
+                (
+                    #![linkme_macro = $macro:path]
+                    #![linkme_override]
+                    #![linkme_element = $element:ty]
+                    #![linkme_info = $info:expr]
+                    $item:item
+                ) => {
+                    $macro ! {
+                        #![linkme_linux_section = #linux_section]
+                        #![linkme_macho_section = #macho_section]
+                        #![linkme_windows_section = #windows_section]
+                        #![linkme_illumos_section = #illumos_section]
+                        #![linkme_bsd_section = #bsd_section]
+                        #[#unsafe_attr(#export_name_attr = #override_symbol)]
+                        $item
+                        #![linkme_info = $info]
+                        #zst_marker
+                    }
+                    #register
+                };
+                (
+                    #![linkme_macro = $macro:path]
+                    #![linkme_element = $element:ty]
+                    #![linkme_info = $info:expr]
+                    $item:item
+                ) => {
+                    ::core::compile_error!(#msg);
+                };
+            }
+        }
+    };
+
+    // Under the fallback backend alone, an element is an ordinary static, and
+    // its info is kept by its registration.
+    let (section_attrs, element_info_item) = match backend {
+        Backend::Both => {
+            let section_attrs = quote! {
+                #[cfg_attr(any(target_os = "none", target_os = "linux", target_os = "android", target_os = "fuchsia", target_os = "psp"), #unsafe_attr(#link_section_attr = $linux_section))]
+                #[cfg_attr(any(target_os = "macos", target_os = "ios", target_os = "tvos"), #unsafe_attr(#link_section_attr = $macho_section))]
+                #[cfg_attr(any(target_os = "uefi", target_os = "windows"), #unsafe_attr(#link_section_attr = $windows_section))]
+                #[cfg_attr(target_os = "illumos", #unsafe_attr(#link_section_attr = $illumos_section))]
+                #[cfg_attr(any(target_os = "freebsd", target_os = "openbsd"), #unsafe_attr(#link_section_attr = $bsd_section))]
             };
+            (section_attrs, element_info_item)
+        }
+        Backend::Fallback => (TokenStream::new(), None),
+    };
+
+    let native_items = match backend {
+        Backend::Both => quote! {
+            #[cfg(any(
+                target_os = "none",
+                target_os = "linux",
+                target_os = "macos",
+                target_os = "ios",
+                target_os = "tvos",
+                target_os = "android",
+                target_os = "fuchsia",
+                target_os = "illumos",
+                target_os = "freebsd",
+                target_os = "openbsd",
+                target_os = "psp",
+            ))]
+            #unsafe_extern extern "Rust" {
+                #[cfg_attr(any(target_os = "none", target_os = "linux", target_os = "android", target_os = "fuchsia", target_os = "psp"), link_name = #linux_section_start)]
+                #[cfg_attr(any(target_os = "macos", target_os = "ios", target_os = "tvos"), link_name = #macho_section_start)]
+                #[cfg_attr(target_os = "illumos", link_name = #illumos_section_start)]
+                #[cfg_attr(any(target_os = "freebsd", target_os = "openbsd"), link_name = #bsd_section_start)]
+                static LINKME_START: [<#ty as #linkme_path::#private::Slice>::Element; 0];
+
+                #[cfg_attr(any(target_os = "none", target_os = "linux", target_os = "android", target_os = "fuchsia", target_os = "psp"), link_name = #linux_section_stop)]
+                #[cfg_attr(any(target_os = "macos", target_os = "ios", target_os = "tvos"), link_name = #macho_section_stop)]
+                #[cfg_attr(target_os = "illumos", link_name = #illumos_section_stop)]
+                #[cfg_attr(any(target_os = "freebsd", target_os = "openbsd"), link_name = #bsd_section_stop)]
+                static LINKME_STOP: [<#ty as #linkme_path::#private::Slice>::Element; 0];
+            }
+
+            #dupcheck
+
+            // On Windows/UEFI, use non-ZST boundary elements because some
+            // codegen backends emit non-zero bytes for ZST statics in custom
+            // PE/COFF sections, which corrupts section boundary and dupcheck
+            // arithmetic.
+            #[cfg(any(target_os = "uefi", target_os = "windows"))]
+            #[#unsafe_attr(#link_section_attr = #windows_section_start)]
+            static LINKME_START: #linkme_path::#private::BoundaryElement<#ty> = #linkme_path::#private::BoundaryElement::<#ty>::uninit();
+
+            #[cfg(any(target_os = "uefi", target_os = "windows"))]
+            #[#unsafe_attr(#link_section_attr = #windows_section_stop)]
+            static LINKME_STOP: #linkme_path::#private::BoundaryElement<#ty> = #linkme_path::#private::BoundaryElement::<#ty>::uninit();
+
+            #linkme_please
+
+            #element_info_decl
+
+            #nonempty_decl
+
+            #bounds_item
+        },
+        Backend::Fallback => TokenStream::new(),
+    };
+
+    let unsupported_platform = if cfg!(feature = "fallback") {
+        None
+    } else {
+        Some(quote! {
+            #[cfg(not(any(
+                target_os = "none",
+                target_os = "linux",
+                target_os = "macos",
+                target_os = "ios",
+                target_os = "tvos",
+                target_os = "windows",
+                target_os = "uefi",
+                target_os = "android",
+                target_os = "fuchsia",
+                target_os = "illumos",
+                target_os = "freebsd",
+                target_os = "openbsd",
+                target_os = "psp",
+                target_family = "wasm",
+            )))]
+            #unsupported_platform
+        })
+    };
+
+    quote! {
+        #invalid_section
+
+        #(#attrs)*
+        #vis static #ident: #static_ty = {
+            #native_items
+
+            #[cfg(#fallback_cfg)]
+            static LINKME_REGISTRY: #linkme_path::#private::Registry =
+                #linkme_path::#private::Registry::new();
+
+            #force_link_item
+
+            #fallback_error
+
+            #unsupported_platform
+
+            #init
+        };
+
+        #type_alias
+
+        #[doc(hidden)]
+        #[macro_export]
+        macro_rules! #link_section_macro {
+            #force_link_arm
+            #entry_arms
             (
                 #![linkme_linux_section = $linux_section:expr]
                 #![linkme_macho_section = $macho_section:expr]
@@ -285,14 +1184,24 @@ pub fn expand(input: TokenStream) -> TokenStream {
                 #![linkme_illumos_section = $illumos_section:expr]
                 #![linkme_bsd_section = $bsd_section:expr]
                 $item:item
+                $(#![linkme_info = $info:expr])?
+                $(#![linkme_zst_marker = $len:expr])?
             ) => {
                 #used
-                #[cfg_attr(any(target_os = "none", target_os = "linux", target_os = "android", target_os = "fuchsia", target_os = "psp"), #unsafe_attr(#link_section_attr = $linux_section))]
-                #[cfg_attr(any(target_os = "macos", target_os = "ios", target_os = "tvos"), #unsafe_attr(#link_section_attr = $macho_section))]
-                #[cfg_attr(any(target_os = "uefi", target_os = "windows"), #unsafe_attr(#link_section_attr = $windows_section))]
-                #[cfg_attr(target_os = "illumos", #unsafe_attr(#link_section_attr = $illumos_section))]
-                #[cfg_attr(any(target_os = "freebsd", target_os = "openbsd"), #unsafe_attr(#link_section_attr = $bsd_section))]
+                #section_attrs
                 $item
+
+                #element_info_item
+
+                $(
+                    #zst_marker_cfg
+                    const _: () = {
+                        #used
+                        #section_attrs
+                        static LINKME_ZST_MARKER: [u8; $len] = [0; $len];
+                    };
+                    #zst_unsupported
+                )?
             };
         }
 
@@ -300,3 +1209,208 @@ pub fn expand(input: TokenStream) -> TokenStream {
         #vis use #link_section_macro as #ident;
     }
 }
+
+fn expand_required_slot(decl: Declaration) -> TokenStream {
+    let mut attrs = decl.attrs;
+    let vis = decl.vis;
+    let ident = decl.ident;
+    let mut ty = decl.ty;
+    let name = ident.to_string();
+
+    let options = match attr::declaration_options(&mut attrs) {
+        Ok(options) => options,
+        Err(err) => return err.to_compile_error(),
+    };
+    let linkme_path = options.linkme_path;
+    if options.force_link {
+        let msg = "force_link is not supported on distributed_slot";
+        return Error::new_spanned(&ident, msg).to_compile_error();
+    }
+    if options.element_info {
+        let msg = "element_info is not supported on distributed_slot";
+        return Error::new_spanned(&ident, msg).to_compile_error();
+    }
+    if let Some(section) = options.section {
+        let msg = "section is only supported on distributed_slot with a default value";
+        return Error::new(section.span(), msg).to_compile_error();
+    }
+
+    ty::populate_static_lifetimes(&mut ty);
+
+    let used = used();
+    let unsafe_extern = unsafe_extern();
+    let (unsafe_attr, _link_section_attr, export_name_attr) = unsafe_attrs();
+
+    // The one definition of the slot exports this symbol. With no definition
+    // the reference below is an undefined symbol, and with more than one the
+    // linker reports multiple definitions of it.
+    let symbol = linker::symbol::slot(&ident);
+
+    let call_site = Span::call_site();
+    let link_section_macro_str = format!("_linkme_macro_{}", ident);
+    let link_section_macro = Ident::new(&link_section_macro_str, call_site);
+
+    let msg = format!(
+        "#[distributed_slot] {} has no default value to override",
+        name,
+    );
+    let force_link_arm = force_link_error_arm(&name);
+
+    quote! {
+        #(#attrs)*
+        #vis static #ident: #linkme_path::DistributedSlot<#ty> = {
+            #unsafe_extern extern "Rust" {
+                #[link_name = #symbol]
+                static LINKME_SLOT: #ty;
+            }
+
+            unsafe {
+                #linkme_path::DistributedSlot::private_new(
+                    #name,
+                    #linkme_path::#private::ptr::addr_of!(LINKME_SLOT),
+                )
+            }
+        };
+
+        #[doc(hidden)]
+        #[macro_export]
+        macro_rules! #link_section_macro {
+            #force_link_arm
+            (
+                #![linkme_macro = $macro:path]
+                #![linkme_element = $element:ty]
+                #![linkme_info = $info:expr]
+                $item:item
+            ) => {
+                #used
+                #[#unsafe_attr(#export_name_attr = #symbol)]
+                $item
+            };
+            (
+                #![linkme_macro = $macro:path]
+                #![linkme_override]
+                #![linkme_element = $element:ty]
+                #![linkme_info = $info:expr]
+                $item:item
+            ) => {
+                ::core::compile_error!(#msg);
+            };
+        }
+
+        #[doc(hidden)]
+        #vis use #link_section_macro as #ident;
+    }
+}
+
+// force_link!(krate => SLICE) naming a declaration without
+// #[linkme(force_link)].
+fn force_link_error_arm(name: &str) -> TokenStream {
+    let msg = format!(
+        "force_link! expects {} to be declared with #[linkme(force_link)]",
+        name,
+    );
+    quote! {
+        (
+            #![linkme_macro = $macro:path]
+            #![linkme_force_link = $krate:literal]
+        ) => {
+            ::core::compile_error!(#msg);
+        };
+    }
+}
+
+// The path to linkme as written inside the declaration's macro_rules, where a
+// path starting with `crate` needs to refer to the declaring crate rather than
+// the crate containing the element.
+fn macro_linkme_path(linkme_path: &Path) -> TokenStream {
+    let mut segments = linkme_path.segments.iter();
+    match segments.next() {
+        Some(first) if linkme_path.leading_colon.is_none() && first.ident == "crate" => {
+            quote!($crate #(:: #segments)*)
+        }
+        _ => quote!(#linkme_path),
+    }
+}
+
+#[derive(Copy, Clone)]
+pub(crate) enum Backend {
+    // Linker sections where supported, and the fallback elsewhere.
+    Both,
+    // The fallback on every target, by `--cfg linkme_fallback`.
+    Fallback,
+}
+
+impl Backend {
+    pub(crate) fn get() -> Self {
+        if cfg!(all(feature = "fallback", linkme_fallback)) {
+            Backend::Fallback
+        } else {
+            Backend::Both
+        }
+    }
+
+    // Targets on which the fallback backend is used. WebAssembly places
+    // statics with a link_section in custom sections outside of linear memory,
+    // so the fallback is its only backend.
+    pub(crate) fn fallback_cfg(self) -> TokenStream {
+        match self {
+            Backend::Both if cfg!(feature = "fallback") => quote! {
+                any(
+                    target_family = "wasm",
+                    not(any(
+                        target_os = "none",
+                        target_os = "linux",
+                        target_os = "macos",
+                        target_os = "ios",
+                        target_os = "tvos",
+                        target_os = "windows",
+                        target_os = "uefi",
+                        target_os = "android",
+                        target_os = "fuchsia",
+                        target_os = "illumos",
+                        target_os = "freebsd",
+                        target_os = "openbsd",
+                        target_os = "psp",
+                    )),
+                )
+            },
+            Backend::Both => quote!(target_family = "wasm"),
+            Backend::Fallback => quote!(all()),
+        }
+    }
+}
+
+fn used() -> TokenStream {
+    if cfg!(feature = "used_linker") {
+        quote!(#[used(linker)])
+    } else {
+        quote!(#[used])
+    }
+}
+
+fn unsafe_extern() -> Option<Token![unsafe]> {
+    if cfg!(no_unsafe_extern_blocks) {
+        None
+    } else {
+        Some(Token![unsafe](Span::call_site()))
+    }
+}
+
+fn unsafe_attrs() -> (Ident, TokenStream, TokenStream) {
+    let call_site = Span::call_site();
+    if cfg!(no_unsafe_attributes) {
+        // #[cfg_attr(all(), link_section = ...)]
+        (
+            Ident::new("cfg_attr", call_site),
+            quote!(all(), link_section),
+            quote!(all(), export_name),
+        )
+    } else {
+        // #[unsafe(link_section = ...)]
+        (
+            Ident::new("unsafe", call_site),
+            quote!(link_section),
+            quote!(export_name),
+        )
+    }
+}
@@ -1,20 +1,28 @@
+use crate::args::ElementArgs;
+use crate::declaration::Backend;
 use crate::{attr, private, ty};
 use proc_macro2::{Span, TokenStream, TokenTree};
 use quote::{format_ident, quote, quote_spanned};
+use syn::buffer::Cursor;
 use syn::parse::{Error, Parse, ParseStream, Result};
 use syn::punctuated::Punctuated;
+use syn::spanned::Spanned as _;
 use syn::{
-    braced, parenthesized, parse_quote, Abi, Attribute, BareFnArg, BoundLifetimes, GenericParam,
-    Generics, Ident, Path, ReturnType, Token, Type, TypeBareFn, Visibility, WhereClause,
+    braced, parenthesized, parse_quote, Abi, Attribute, BareFnArg, BoundLifetimes, Expr,
+    GenericParam, Generics, Ident, Lifetime, Path, ReturnType, Token, Type, TypeBareFn, TypeInfer,
+    Visibility, WhereClause,
 };
 
+#[derive(Clone)]
 pub struct Element {
     attrs: Vec<Attribute>,
     vis: Visibility,
     ident: Ident,
+    name: String,
     ty: Type,
     expr: TokenStream,
     orig_item: Option<TokenStream>,
+    type_params: Vec<GenericParam>,
     start_span: Span,
     end_span: Span,
 }
@@ -44,25 +52,34 @@ impl Parse for Element {
                 syn::parse2::<Token![;]>(TokenStream::from(tail))?;
             }
             let expr = TokenStream::from_iter(expr_semi);
+            let name = ident.to_string();
             Ok(Element {
                 attrs,
                 vis,
                 ident,
+                name,
                 ty,
                 expr,
                 orig_item: None,
+                type_params: Vec::new(),
                 start_span,
                 end_span,
             })
+        } else if input.peek(Token![impl])
+            || input.peek(Token![unsafe]) && input.peek2(Token![impl])
+        {
+            parse_impl(attrs, item, input)
+        } else if input.peek(Token![struct]) {
+            parse_unit_struct(attrs, item, input)
         } else {
-            let constness: Option<Token![const]> = input.parse()?;
+            input.parse::<Option<Token![const]>>()?;
             let asyncness: Option<Token![async]> = input.parse()?;
             let unsafety: Option<Token![unsafe]> = input.parse()?;
             let abi: Option<Abi> = input.parse()?;
             let fn_token: Token![fn] = input.parse().map_err(|_| {
                 Error::new_spanned(
                     item.token_stream(),
-                    "distributed element must be either static or function item",
+                    "distributed element must be a static, function, impl, or unit struct item",
                 )
             })?;
             let ident: Ident = input.parse()?;
@@ -72,19 +89,28 @@ impl Parse for Element {
             let paren_token = parenthesized!(content in input);
             let mut inputs = Punctuated::new();
             while !content.is_empty() {
-                content.parse::<Option<Token![mut]>>()?;
-                let ident = if let Some(wild) = content.parse::<Option<Token![_]>>()? {
-                    Ident::from(wild)
+                if inputs.is_empty() && is_receiver(&content) {
+                    let ty = parse_receiver(&content)?;
+                    inputs.push_value(BareFnArg {
+                        attrs: Vec::new(),
+                        name: None,
+                        ty,
+                    });
                 } else {
-                    content.parse()?
-                };
-                let colon_token: Token![:] = content.parse()?;
-                let ty: Type = content.parse()?;
-                inputs.push_value(BareFnArg {
-                    attrs: Vec::new(),
-                    name: Some((ident, colon_token)),
-                    ty,
-                });
+                    content.parse::<Option<Token![mut]>>()?;
+                    let ident = if let Some(wild) = content.parse::<Option<Token![_]>>()? {
+                        Ident::from(wild)
+                    } else {
+                        content.parse()?
+                    };
+                    let colon_token: Token![:] = content.parse()?;
+                    let ty: Type = content.parse()?;
+                    inputs.push_value(BareFnArg {
+                        attrs: Vec::new(),
+                        name: Some((ident, colon_token)),
+                        ty,
+                    });
+                }
                 if !content.is_empty() {
                     let comma: Token![,] = content.parse()?;
                     inputs.push_punct(comma);
@@ -98,21 +124,29 @@ impl Parse for Element {
             braced!(content in input);
             content.parse::<TokenStream>()?;
 
-            if let Some(constness) = constness {
-                return Err(Error::new_spanned(
-                    constness,
-                    "const fn distributed slice element is not supported",
-                ));
-            }
-
             if let Some(asyncness) = asyncness {
-                return Err(Error::new_spanned(
-                    asyncness,
-                    "async fn distributed slice element is not supported",
-                ));
+                if !cfg!(feature = "alloc") {
+                    return Err(Error::new_spanned(
+                        asyncness,
+                        "async fn distributed slice element requires the \"alloc\" feature of linkme",
+                    ));
+                }
+                if let Some(unsafety) = unsafety {
+                    return Err(Error::new_spanned(
+                        unsafety,
+                        "unsafe async fn distributed slice element is not supported",
+                    ));
+                }
+                if !generics.params.is_empty() {
+                    return Err(Error::new_spanned(
+                        generics.params,
+                        "cannot have generic parameters on async fn distributed slice element",
+                    ));
+                }
             }
 
-            let lifetimes = if generics.params.is_empty() {
+            let mut type_params = Vec::new();
+            let mut lifetimes = if generics.params.is_empty() {
                 None
             } else {
                 let mut bound = BoundLifetimes {
@@ -123,22 +157,28 @@ impl Parse for Element {
                 };
                 for param in generics.params.into_pairs() {
                     let (param, punct) = param.into_tuple();
-                    if let GenericParam::Lifetime(_) = param {
-                        bound.lifetimes.push_value(param);
-                        if let Some(punct) = punct {
-                            bound.lifetimes.push_punct(punct);
+                    match param {
+                        GenericParam::Lifetime(_) => {
+                            bound.lifetimes.push_value(param);
+                            if let Some(punct) = punct {
+                                bound.lifetimes.push_punct(punct);
+                            }
+                        }
+                        GenericParam::Type(_) => type_params.push(param),
+                        GenericParam::Const(_) => {
+                            return Err(Error::new_spanned(
+                                param,
+                                "cannot have generic parameters on distributed slice element",
+                            ));
                         }
-                    } else {
-                        return Err(Error::new_spanned(
-                            param,
-                            "cannot have generic parameters on distributed slice element",
-                        ));
                     }
                 }
                 Some(bound)
             };
 
-            if let Some(where_clause) = where_clause {
+            // The bounds of type parameters are checked by instantiation,
+            // which does not involve the fn pointer type.
+            if let Some(where_clause) = where_clause.filter(|_| type_params.is_empty()) {
                 return Err(Error::new_spanned(
                     where_clause,
                     "where-clause is not allowed on distributed slice elements",
@@ -163,7 +203,51 @@ impl Parse for Element {
                 },
             ];
             let vis = Visibility::Inherited;
-            let expr = parse_quote!(#ident);
+            let mut expr = quote!(#ident);
+            let mut output = output;
+
+            // An async fn is registered through an adapter that boxes the
+            // future it returns. The future borrows whatever the arguments
+            // borrow, so their elided lifetimes are named by one lifetime which
+            // also bounds the future.
+            if asyncness.is_some() {
+                let borrow = Lifetime::new("'__linkme", Span::call_site());
+                let mut borrows = false;
+                for arg in &mut inputs {
+                    borrows |= ty::name_elided_lifetimes(&mut arg.ty, &borrow);
+                }
+                let mut future_output = match &output {
+                    ReturnType::Default => parse_quote!(()),
+                    ReturnType::Type(_, ty) => Type::clone(ty),
+                };
+                let (params, bound) = if borrows {
+                    ty::name_elided_lifetimes(&mut future_output, &borrow);
+                    lifetimes = Some(parse_quote!(for<#borrow>));
+                    (Some(quote!(<#borrow>)), Some(quote!(+ #borrow)))
+                } else {
+                    (None, None)
+                };
+                let future: Type = parse_quote! {
+                    #linkme_path::#private::Pin<
+                        #linkme_path::#private::Box<
+                            dyn #linkme_path::#private::Future<Output = #future_output>
+                                + #linkme_path::#private::Send
+                                #bound
+                        >
+                    >
+                };
+                let args = (0..inputs.len()).map(|i| format_ident!("__arg{}", i));
+                let arg_tys = inputs.iter().map(|arg| &arg.ty);
+                let call_args = args.clone();
+                expr = quote! {{
+                    #abi fn __linkme_async #params(#(#args: #arg_tys),*) -> #future {
+                        #linkme_path::#private::Box::pin(#ident(#(#call_args),*))
+                    }
+                    __linkme_async
+                }};
+                output = parse_quote!(-> #future);
+            }
+
             let ty = Type::BareFn(TypeBareFn {
                 lifetimes,
                 unsafety,
@@ -174,6 +258,7 @@ impl Parse for Element {
                 variadic: None,
                 output,
             });
+            let name = ident.to_string();
             let ident = format_ident!("_LINKME_ELEMENT_{}", ident);
             let item = item.token_stream();
             let orig_item = Some(quote!(
@@ -185,9 +270,11 @@ impl Parse for Element {
                 attrs,
                 vis,
                 ident,
+                name,
                 ty,
                 expr,
                 orig_item,
+                type_params,
                 start_span,
                 end_span,
             })
@@ -195,19 +282,315 @@ impl Parse for Element {
     }
 }
 
-pub fn expand(path: Path, pos: impl Into<Option<usize>>, input: Element) -> TokenStream {
-    let pos = pos.into();
-    do_expand(path, pos, input)
+fn is_receiver(input: ParseStream) -> bool {
+    input.peek(Token![self])
+        || input.peek(Token![mut]) && input.peek2(Token![self])
+        || input.peek(Token![&])
+}
+
+// self, mut self, &self, &'a mut self, self: Box<Self>
+//
+// Only methods registered through #[linkme::register] have a receiver.
+fn parse_receiver(input: ParseStream) -> Result<Type> {
+    let reference: Option<Token![&]> = input.parse()?;
+    let lifetime: Option<Lifetime> = if reference.is_some() {
+        input.parse()?
+    } else {
+        None
+    };
+    let mutability: Option<Token![mut]> = input.parse()?;
+    let self_token: Token![self] = input.parse()?;
+    let self_ty = Token![Self](self_token.span);
+    if reference.is_none() && input.peek(Token![:]) {
+        input.parse::<Token![:]>()?;
+        return input.parse();
+    }
+    Ok(match reference {
+        Some(reference) => parse_quote!(#reference #lifetime #mutability #self_ty),
+        None => parse_quote!(#self_ty),
+    })
+}
+
+// impl Trait for Type { ... }
+//
+// The element is `&Type`, so Type must be a unit struct. Its type is left to be
+// inferred from the slice: see do_expand.
+fn parse_impl(attrs: Vec<Attribute>, item: Cursor, input: ParseStream) -> Result<Element> {
+    input.parse::<Option<Token![unsafe]>>()?;
+    input.parse::<Token![impl]>()?;
+    let generics: Generics = input.parse()?;
+    if !generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            generics.params,
+            "cannot have generic parameters on distributed slice element",
+        ));
+    }
+    let trait_path: Path = input.parse()?;
+    input.parse::<Token![for]>()?;
+    let self_ty: Type = input.parse()?;
+    let where_clause: Option<WhereClause> = input.parse()?;
+    if let Some(where_clause) = where_clause {
+        return Err(Error::new_spanned(
+            where_clause,
+            "where-clause is not allowed on distributed slice elements",
+        ));
+    }
+    let content;
+    braced!(content in input);
+    content.parse::<TokenStream>()?;
+
+    let self_segment = match &self_ty {
+        Type::Path(ty) if ty.qself.is_none() => ty.path.segments.last().unwrap(),
+        _ => return Err(unit_struct_error(&self_ty)),
+    };
+    if !self_segment.arguments.is_empty() {
+        return Err(unit_struct_error(&self_ty));
+    }
+
+    let self_ident = &self_segment.ident;
+    let trait_ident = &trait_path.segments.last().unwrap().ident;
+    let ident = format_ident!("_LINKME_ELEMENT_{}_{}", self_ident, trait_ident);
+    let expr = quote!(&#self_ty);
+    Element::new_inferred(attrs, ident, self_ident.to_string(), expr, item)
+}
+
+// struct Type;
+fn parse_unit_struct(attrs: Vec<Attribute>, item: Cursor, input: ParseStream) -> Result<Element> {
+    let struct_token: Token![struct] = input.parse()?;
+    let self_ident: Ident = input.parse()?;
+    let generics: Generics = input.parse()?;
+    if !generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            generics.params,
+            "cannot have generic parameters on distributed slice element",
+        ));
+    }
+    if !input.peek(Token![;]) {
+        return Err(Error::new_spanned(
+            quote!(#struct_token #self_ident),
+            "distributed slice element must be a unit struct",
+        ));
+    }
+    input.parse::<Token![;]>()?;
+
+    let ident = format_ident!("_LINKME_ELEMENT_{}", self_ident);
+    let expr = quote!(&#self_ident);
+    Element::new_inferred(attrs, ident, self_ident.to_string(), expr, item)
+}
+
+fn unit_struct_error(self_ty: &Type) -> Error {
+    Error::new_spanned(
+        self_ty,
+        "distributed slice element must be implemented for a unit struct",
+    )
+}
+
+impl Element {
+    // A function inside an impl block, which is registered by a path qualified
+    // with the impl's self type. Its signature may involve `Self`, so the type
+    // of the element is taken from the slice.
+    pub fn into_method(self, qself: &TokenStream, linkme_path: &Path) -> Self {
+        let expr = &self.expr;
+        Element {
+            attrs: vec![
+                parse_quote! {
+                    #[allow(non_upper_case_globals)]
+                },
+                parse_quote! {
+                    #[linkme(crate = #linkme_path)]
+                },
+            ],
+            ty: parse_quote!(_),
+            expr: quote!(#qself::#expr),
+            orig_item: None,
+            ..self
+        }
+    }
+
+    // An element that is a reference to a unit struct, with the original item
+    // passed through unchanged.
+    fn new_inferred(
+        mut original_attrs: Vec<Attribute>,
+        ident: Ident,
+        name: String,
+        expr: TokenStream,
+        item: Cursor,
+    ) -> Result<Self> {
+        let linkme_path = attr::linkme_path(&mut original_attrs)?;
+        let attrs = vec![
+            parse_quote! {
+                #[allow(non_upper_case_globals)]
+            },
+            parse_quote! {
+                #[linkme(crate = #linkme_path)]
+            },
+        ];
+        let span = item.span();
+        let item = item.token_stream();
+        Ok(Element {
+            attrs,
+            vis: Visibility::Inherited,
+            ident,
+            name,
+            ty: Type::Infer(TypeInfer {
+                underscore_token: Token![_](span),
+            }),
+            expr,
+            orig_item: Some(quote! {
+                #(#original_attrs)*
+                #item
+            }),
+            type_params: Vec::new(),
+            start_span: span,
+            end_span: span,
+        })
+    }
+}
+
+// SLICE => EXPR
+pub struct AnonymousElement {
+    path: Path,
+    element: Element,
+}
+
+impl Parse for AnonymousElement {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut attrs = input.call(Attribute::parse_outer)?;
+        let path: Path = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let expr: TokenStream = input.parse()?;
+        if expr.is_empty() {
+            return Err(input.error("expected an expression"));
+        }
+
+        // The element type is named through the type alias exported by the
+        // slice's declaration next to the slice.
+        attrs.push(parse_quote!(#[allow(non_upper_case_globals)]));
+        let ty = ty::element_alias_path(&path);
+        let span = path.span();
+        Ok(AnonymousElement {
+            path,
+            element: Element {
+                attrs,
+                vis: Visibility::Inherited,
+                ident: Ident::new("LINKME_ELEMENT", Span::call_site()),
+                name: "_".to_owned(),
+                ty,
+                expr,
+                orig_item: None,
+                type_params: Vec::new(),
+                start_span: span,
+                end_span: span,
+            },
+        })
+    }
 }
 
-fn do_expand(path: Path, pos: Option<usize>, input: Element) -> TokenStream {
+#[derive(Copy, Clone)]
+enum Kind {
+    Slice,
+    Slot,
+    SlotOverride,
+}
+
+pub fn expand(args: ElementArgs, mut input: Element) -> TokenStream {
+    let orig_item = input.orig_item.take();
+
+    // A generic function contributes one element per listed instantiation,
+    // each taking on the element type of the slice.
+    let instances = if args.instantiate.is_empty() {
+        vec![input]
+    } else if input.type_params.len() != 1 {
+        let msg = "instantiate(...) requires a function with exactly one type parameter";
+        return Error::new(input.start_span, msg).to_compile_error();
+    } else {
+        let expr = &input.expr;
+        args.instantiate
+            .iter()
+            .map(|ty| Element {
+                ty: parse_quote!(_),
+                expr: quote!(#expr::<#ty>),
+                type_params: Vec::new(),
+                ..input.clone()
+            })
+            .collect()
+    };
+
+    let mut expanded = TokenStream::new();
+    for (i, path) in args.paths.iter().enumerate() {
+        for (j, instance) in instances.iter().enumerate() {
+            let mut element = instance.clone();
+            if i + j > 0 {
+                element.vis = Visibility::Inherited;
+            }
+            let element = do_expand(path.clone(), args.pos, element, Kind::Slice);
+            if i + j == 0 {
+                expanded.extend(element);
+            } else {
+                // Every element after the first is scoped so that its name does
+                // not collide with the first.
+                expanded.extend(quote! {
+                    const _: () = {
+                        #element
+                    };
+                });
+            }
+        }
+    }
+
+    quote! {
+        #expanded
+        #orig_item
+    }
+}
+
+pub fn expand_anonymous(input: AnonymousElement) -> TokenStream {
+    let expanded = do_expand(input.path, None, input.element, Kind::Slice);
+    quote! {
+        const _: () = {
+            #expanded
+        };
+    }
+}
+
+pub fn expand_slot(path: Path, is_override: bool, input: Element) -> TokenStream {
+    let kind = if is_override {
+        Kind::SlotOverride
+    } else {
+        Kind::Slot
+    };
+    do_expand(path, None, input, kind)
+}
+
+const INFERRED_UNSUPPORTED: &str =
+    "impl and unit struct elements are only supported by distributed_slice";
+
+fn do_expand(path: Path, pos: Option<usize>, input: Element, kind: Kind) -> TokenStream {
+    if let Some(param) = input.type_params.first() {
+        let msg = "cannot have generic parameters on distributed slice element";
+        return Error::new_spanned(param, msg).to_compile_error();
+    }
+
     let mut attrs = input.attrs;
     let vis = input.vis;
     let ident = input.ident;
+    let name = input.name;
     let mut ty = input.ty;
     let expr = input.expr;
     let orig_item = input.orig_item;
 
+    // An impl or unit struct element, or an instantiation of a generic
+    // function, takes on the element type of the slice, which the slice's
+    // declaration exports next to the slice.
+    if let Type::Infer(_) = ty {
+        match kind {
+            Kind::Slice => ty = ty::element_alias_path(&path),
+            Kind::Slot | Kind::SlotOverride => {
+                return Error::new(input.start_span, INFERRED_UNSUPPORTED).to_compile_error();
+            }
+        }
+    }
+
     ty::populate_static_lifetimes(&mut ty);
 
     let linkme_path = match attr::linkme_path(&mut attrs) {
@@ -215,8 +598,47 @@ fn do_expand(path: Path, pos: Option<usize>, input: Element) -> TokenStream {
         Err(err) => return err.to_compile_error(),
     };
 
-    let sort_key = pos.into_iter().map(|pos| format!("{:04}", pos));
+    let sort_key = pos.map(|pos| format!("{:04}", pos));
+
+    // Only the PE/COFF linker sorts grouped sections by the suffix after `$`,
+    // and the fallback backend sorts elements by position when it collects
+    // them. No other linker can sort the sections of a slice, so a positioned
+    // element is rejected there.
+    let unsupported_sort_key = pos.map(|_| {
+        let fallback_cfg = Backend::get().fallback_cfg();
+        let msg = "ordering of distributed slice elements is only supported on Windows, UEFI, and the fallback backend";
+        quote_spanned! {path.span()=>
+            #[cfg(not(any(target_os = "uefi", target_os = "windows", #fallback_cfg)))]
+            ::core::compile_error!(#msg);
+        }
+    });
+
+    let position = if let Some(pos) = pos {
+        quote!(#linkme_path::#private::Option::Some(#pos))
+    } else {
+        quote!(#linkme_path::#private::Option::None)
+    };
+    let info = quote! {
+        #linkme_path::ElementInfo::private_new(
+            #linkme_path::#private::ptr::addr_of!(#ident).cast::<()>(),
+            #name,
+            #linkme_path::#private::module_path!(),
+            #linkme_path::#private::file!(),
+            #linkme_path::#private::line!(),
+            #position,
+        )
+    };
+
+    let container = match kind {
+        Kind::Slice => quote!(DistributedSlice),
+        Kind::Slot | Kind::SlotOverride => quote!(DistributedSlot),
+    };
+    let linkme_override = match kind {
+        Kind::Slice | Kind::Slot => None,
+        Kind::SlotOverride => Some(quote!(#![linkme_override])),
+    };
 
+    let sort_key = sort_key.iter();
     let factory = quote_spanned!(input.start_span=> __new);
     let get = quote_spanned!(input.end_span=> #factory());
 
@@ -226,6 +648,9 @@ fn do_expand(path: Path, pos: Option<usize>, input: Element) -> TokenStream {
             #(
                 #![linkme_sort_key = #sort_key]
             )*
+            #linkme_override
+            #![linkme_element = #ty]
+            #![linkme_info = #info]
             #(#attrs)*
             #vis static #ident : #ty = {
                 #[allow(clippy::no_effect_underscore_binding)]
@@ -233,7 +658,7 @@ fn do_expand(path: Path, pos: Option<usize>, input: Element) -> TokenStream {
                     #[allow(clippy::ref_option_ref)]
                     let #factory = || -> fn() -> &'static #ty { || &#ident };
                     unsafe {
-                        #linkme_path::DistributedSlice::private_typecheck(#path, #get);
+                        #linkme_path::#container::private_typecheck(#path, #get);
                     }
                 }
 
@@ -241,6 +666,74 @@ fn do_expand(path: Path, pos: Option<usize>, input: Element) -> TokenStream {
             };
         }
 
+        #unsupported_sort_key
+        #orig_item
+    }
+}
+
+pub fn expand_map(path: Path, key: Expr, input: Element) -> TokenStream {
+    if let Some(param) = input.type_params.first() {
+        let msg = "cannot have generic parameters on distributed map element";
+        return Error::new_spanned(param, msg).to_compile_error();
+    }
+
+    let mut attrs = input.attrs;
+    let vis = input.vis;
+    let ident = input.ident;
+    let name = input.name;
+    let mut ty = input.ty;
+    let expr = input.expr;
+    let orig_item = input.orig_item;
+
+    if let Type::Infer(_) = ty {
+        return Error::new(input.start_span, INFERRED_UNSUPPORTED).to_compile_error();
+    }
+
+    ty::populate_static_lifetimes(&mut ty);
+
+    let linkme_path = match attr::linkme_path(&mut attrs) {
+        Ok(path) => path,
+        Err(err) => return err.to_compile_error(),
+    };
+
+    // The element's own static stays outside of the map's section, which
+    // instead receives an entry referring to it. The entry's type is the map
+    // entry type exported by the declaration next to the map, so the key and
+    // value are type checked by the entry's initializer.
+    let entry = format_ident!("_LINKME_MAP_ENTRY_{}", name);
+    let entry_ty = ty::element_alias_path(&path);
+    let info = quote! {
+        #linkme_path::ElementInfo::private_new(
+            #linkme_path::#private::ptr::addr_of!(#entry).cast::<()>(),
+            #name,
+            #linkme_path::#private::module_path!(),
+            #linkme_path::#private::file!(),
+            #linkme_path::#private::line!(),
+            #linkme_path::#private::Option::None,
+        )
+    };
+
+    quote! {
+        #(#attrs)*
+        #vis static #ident : #ty = #expr;
+
+        const _: () = {
+            #path ! {
+                #![linkme_macro = #path]
+                #![linkme_element = #entry_ty]
+                #![linkme_info = #info]
+                #[allow(non_upper_case_globals)]
+                static #entry: #entry_ty = #linkme_path::#private::MapEntry::private_new(
+                    #key,
+                    &#ident,
+                    #name,
+                    #linkme_path::#private::module_path!(),
+                    #linkme_path::#private::file!(),
+                    #linkme_path::#private::line!(),
+                );
+            }
+        };
+
         #orig_item
     }
 }
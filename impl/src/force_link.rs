@@ -0,0 +1,68 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::parse::{Error, Parse, ParseStream, Result};
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Ident, Path, Token};
+
+pub struct ForceLink {
+    crates: Punctuated<ForceLinkCrate, Token![,]>,
+}
+
+// `krate` or `krate => SLICE`.
+struct ForceLinkCrate {
+    krate: Ident,
+    slice: Option<Path>,
+}
+
+impl Parse for ForceLink {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let attrs = input.call(Attribute::parse_outer)?;
+        if let Some(attr) = attrs.first() {
+            return Err(Error::new_spanned(attr, "unsupported attribute"));
+        }
+        let crates = Punctuated::parse_terminated(input)?;
+        if crates.is_empty() {
+            return Err(input.error("expected at least one crate name"));
+        }
+        Ok(ForceLink { crates })
+    }
+}
+
+impl Parse for ForceLinkCrate {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let krate: Ident = input.parse()?;
+        let slice = if input.peek(Token![=>]) {
+            input.parse::<Token![=>]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(ForceLinkCrate { krate, slice })
+    }
+}
+
+// Loads each crate, which is otherwise left out of the link when nothing in
+// it is referenced. For `krate => SLICE`, the slice's declaration also records
+// that the crate is expected to contribute to it, for a declaration with
+// #[linkme(force_link)] to check against the first segment of the module path
+// of each element.
+pub fn expand(input: ForceLink) -> TokenStream {
+    let mut expanded = TokenStream::new();
+    for entry in &input.crates {
+        let krate = &entry.krate;
+        expanded.extend(quote! {
+            #[allow(unused_extern_crates)]
+            extern crate #krate as _;
+        });
+        if let Some(slice) = &entry.slice {
+            let name = krate.to_string();
+            expanded.extend(quote! {
+                #slice! {
+                    #![linkme_macro = #slice]
+                    #![linkme_force_link = #name]
+                }
+            });
+        }
+    }
+    expanded
+}
@@ -10,15 +10,19 @@ mod args;
 mod attr;
 mod declaration;
 mod element;
+mod force_link;
 mod hash;
 mod linker;
+mod register;
+mod section;
 mod ty;
 
-use crate::args::Args;
+use crate::args::{Args, MapArgs, SlotArgs};
 use crate::hash::hash;
 use proc_macro::TokenStream;
 use proc_macro2::{Ident, Span};
 use quote::{ToTokens, TokenStreamExt as _};
+use syn::parse::Nothing;
 use syn::parse_macro_input;
 
 #[proc_macro_attribute]
@@ -26,14 +30,59 @@ pub fn distributed_slice(args: TokenStream, input: TokenStream) -> TokenStream {
     let args = parse_macro_input!(args as Args);
 
     let expanded = match args {
-        Args::None => declaration::expand(parse_macro_input!(input)),
-        Args::Path(path) => element::expand(path, None, parse_macro_input!(input)),
-        Args::PathPos(path, pos) => element::expand(path, pos, parse_macro_input!(input)),
+        Args::Declaration(cardinality) => {
+            declaration::expand(cardinality, parse_macro_input!(input))
+        }
+        Args::ExternSection(section, cardinality) => {
+            declaration::expand_extern(section, cardinality, parse_macro_input!(input))
+        }
+        Args::Element(args) => element::expand(args, parse_macro_input!(input)),
     };
 
     TokenStream::from(expanded)
 }
 
+#[proc_macro]
+pub fn distributed_slice_element(input: TokenStream) -> TokenStream {
+    TokenStream::from(element::expand_anonymous(parse_macro_input!(input)))
+}
+
+#[proc_macro]
+pub fn force_link(input: TokenStream) -> TokenStream {
+    TokenStream::from(force_link::expand(parse_macro_input!(input)))
+}
+
+#[proc_macro_attribute]
+pub fn distributed_map(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as MapArgs);
+
+    let expanded = match args {
+        MapArgs::None => declaration::expand_map(parse_macro_input!(input)),
+        MapArgs::PathKey(path, key) => element::expand_map(path, *key, parse_macro_input!(input)),
+    };
+
+    TokenStream::from(expanded)
+}
+
+#[proc_macro_attribute]
+pub fn distributed_slot(args: TokenStream, input: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(args as SlotArgs);
+
+    let expanded = match args {
+        SlotArgs::None => declaration::expand_slot(parse_macro_input!(input)),
+        SlotArgs::Path(path) => element::expand_slot(path, false, parse_macro_input!(input)),
+        SlotArgs::PathOverride(path) => element::expand_slot(path, true, parse_macro_input!(input)),
+    };
+
+    TokenStream::from(expanded)
+}
+
+#[proc_macro_attribute]
+pub fn register(args: TokenStream, input: TokenStream) -> TokenStream {
+    parse_macro_input!(args as Nothing);
+    TokenStream::from(register::expand(parse_macro_input!(input)))
+}
+
 #[allow(non_camel_case_types)]
 struct private;
 
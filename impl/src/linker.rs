@@ -1,82 +1,121 @@
 pub mod linux {
+    use crate::section::{self, Role};
     use syn::Ident;
 
-    pub fn section(ident: &Ident) -> String {
-        format!("linkme_{}", ident)
+    pub(crate) fn section(role: Role, ident: &Ident) -> String {
+        section::elf(role, &ident.to_string(), &crate::hash(ident))
     }
 
-    pub fn section_start(ident: &Ident) -> String {
-        format!("__start_linkme_{}", ident)
+    pub(crate) fn section_start(role: Role, ident: &Ident) -> String {
+        format!("__start_{}", section(role, ident))
     }
 
-    pub fn section_stop(ident: &Ident) -> String {
-        format!("__stop_linkme_{}", ident)
+    pub(crate) fn section_stop(role: Role, ident: &Ident) -> String {
+        format!("__stop_{}", section(role, ident))
     }
 }
 
 pub mod bsd {
+    use crate::section::{self, Role};
     use syn::Ident;
 
-    pub fn section(ident: &Ident) -> String {
-        format!("linkme_{}", ident)
+    pub(crate) fn section(role: Role, ident: &Ident) -> String {
+        section::elf(role, &ident.to_string(), &crate::hash(ident))
     }
 
-    pub fn section_start(ident: &Ident) -> String {
-        format!("__start_linkme_{}", ident)
+    pub(crate) fn section_start(role: Role, ident: &Ident) -> String {
+        format!("__start_{}", section(role, ident))
     }
 
-    pub fn section_stop(ident: &Ident) -> String {
-        format!("__stop_linkme_{}", ident)
+    pub(crate) fn section_stop(role: Role, ident: &Ident) -> String {
+        format!("__stop_{}", section(role, ident))
     }
 }
 
 pub mod macho {
+    use crate::section::{self, Role};
     use syn::Ident;
 
-    pub fn section(ident: &Ident) -> String {
-        format!(
-            "__DATA,__linkme{},regular,no_dead_strip",
-            crate::hash(ident),
-        )
+    fn name(role: Role, ident: &Ident) -> String {
+        section::macho(role, &crate::hash(ident))
     }
 
-    pub fn section_start(ident: &Ident) -> String {
-        format!("\x01section$start$__DATA$__linkme{}", crate::hash(ident))
+    pub(crate) fn section(role: Role, ident: &Ident) -> String {
+        format!("__DATA,{},regular,no_dead_strip", name(role, ident))
     }
 
-    pub fn section_stop(ident: &Ident) -> String {
-        format!("\x01section$end$__DATA$__linkme{}", crate::hash(ident))
+    pub(crate) fn section_start(role: Role, ident: &Ident) -> String {
+        format!("\x01section$start$__DATA${}", name(role, ident))
+    }
+
+    pub(crate) fn section_stop(role: Role, ident: &Ident) -> String {
+        format!("\x01section$end$__DATA${}", name(role, ident))
     }
 }
 
 pub mod windows {
+    use crate::section::{self, Role};
     use syn::Ident;
 
-    pub fn section(ident: &Ident) -> String {
-        format!(".linkme_{}$b", ident)
+    fn name(role: Role, ident: &Ident) -> String {
+        section::windows(role, &ident.to_string(), &crate::hash(ident))
     }
 
-    pub fn section_start(ident: &Ident) -> String {
-        format!(".linkme_{}$a", ident)
+    pub(crate) fn section(role: Role, ident: &Ident) -> String {
+        format!("{}$b", name(role, ident))
     }
 
-    pub fn section_stop(ident: &Ident) -> String {
-        format!(".linkme_{}$c", ident)
+    pub(crate) fn section_start(role: Role, ident: &Ident) -> String {
+        format!("{}$a", name(role, ident))
+    }
+
+    pub(crate) fn section_stop(role: Role, ident: &Ident) -> String {
+        format!("{}$c", name(role, ident))
     }
 }
 
 pub mod illumos {
+    use crate::section::{self, Role};
     use syn::Ident;
 
-    pub fn section(ident: &Ident) -> String {
-        format!("set_linkme_{}", ident)
+    pub(crate) fn section(role: Role, ident: &Ident) -> String {
+        section::illumos(role, &ident.to_string(), &crate::hash(ident))
+    }
+
+    pub(crate) fn section_start(role: Role, ident: &Ident) -> String {
+        format!("__start_{}", section(role, ident))
+    }
+
+    pub(crate) fn section_stop(role: Role, ident: &Ident) -> String {
+        format!("__stop_{}", section(role, ident))
+    }
+}
+
+pub mod symbol {
+    use crate::section;
+    use syn::Ident;
+
+    pub fn duplicate(ident: &Ident) -> String {
+        section::duplicate_symbol(&ident.to_string(), &crate::hash(ident))
+    }
+
+    pub fn bounds(ident: &Ident) -> String {
+        section::bounds_symbol(&ident.to_string(), &crate::hash(ident))
+    }
+
+    pub fn nonempty(ident: &Ident) -> String {
+        section::nonempty_symbol(&ident.to_string(), &crate::hash(ident))
+    }
+
+    pub fn too_many(ident: &Ident) -> String {
+        section::too_many_symbol(&ident.to_string(), &crate::hash(ident))
     }
 
-    pub fn section_start(ident: &Ident) -> String {
-        format!("__start_set_linkme_{}", ident)
+    pub fn slot(ident: &Ident) -> String {
+        section::slot_symbol(&ident.to_string(), &crate::hash(ident))
     }
 
-    pub fn section_stop(ident: &Ident) -> String {
-        format!("__stop_set_linkme_{}", ident)
+    pub fn slot_override(ident: &Ident) -> String {
+        section::slot_override_symbol(&ident.to_string(), &crate::hash(ident))
     }
 }
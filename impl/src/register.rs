@@ -0,0 +1,173 @@
+use crate::args::{Args, ElementArgs};
+use crate::attr;
+use crate::element::{self, Element};
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use quote::quote;
+use syn::buffer::Cursor;
+use syn::parse::{Error, Parse, ParseStream, Result};
+use syn::{braced, Attribute, Generics, Token, Type, Visibility, WhereClause};
+
+pub struct Register {
+    header: TokenStream,
+    inner_attrs: Vec<Attribute>,
+    items: Vec<TokenStream>,
+    methods: Vec<(ElementArgs, Element)>,
+}
+
+impl Parse for Register {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let begin = input.cursor();
+        input.call(Attribute::parse_outer)?;
+        input.parse::<Option<Token![unsafe]>>()?;
+        input.parse::<Token![impl]>()?;
+        let generics: Generics = input.parse()?;
+        if !generics.params.is_empty() {
+            return Err(Error::new_spanned(
+                generics.params,
+                "cannot register functions from a generic impl block",
+            ));
+        }
+        let ty: Type = input.parse()?;
+        let qself = if input.peek(Token![for]) {
+            input.parse::<Token![for]>()?;
+            let self_ty: Type = input.parse()?;
+            quote!(<#self_ty as #ty>)
+        } else {
+            quote!(<#ty>)
+        };
+        input.parse::<Option<WhereClause>>()?;
+        let header = tokens_between(begin, input.cursor());
+
+        let content;
+        braced!(content in input);
+        let inner_attrs = content.call(Attribute::parse_inner)?;
+
+        let mut items = Vec::new();
+        let mut methods = Vec::new();
+        while !content.is_empty() {
+            let mut attrs = content.call(Attribute::parse_outer)?;
+            let mut slices = Vec::new();
+            let mut errors: Option<Error> = None;
+            attrs.retain(|attr| {
+                let path = attr.path();
+                if path.segments.last().unwrap().ident != "distributed_slice" {
+                    return true;
+                }
+                match attr.parse_args::<Args>() {
+                    Ok(Args::Element(args)) => slices.push(args),
+                    Ok(Args::Declaration(_) | Args::ExternSection(..)) => {
+                        let msg = "expected path to a distributed slice";
+                        let err = Error::new_spanned(attr, msg);
+                        match &mut errors {
+                            None => errors = Some(err),
+                            Some(errors) => errors.combine(err),
+                        }
+                    }
+                    Err(err) => match &mut errors {
+                        None => errors = Some(err),
+                        Some(errors) => errors.combine(err),
+                    },
+                }
+                false
+            });
+            if let Some(errors) = errors {
+                return Err(errors);
+            }
+
+            let begin = content.cursor();
+            if slices.is_empty() {
+                skip_item(&content)?;
+            } else {
+                let ahead = content.fork();
+                ahead.parse::<Visibility>()?;
+                ahead.parse::<Option<Token![const]>>()?;
+                if let Some(asyncness) = ahead.parse::<Option<Token![async]>>()? {
+                    return Err(Error::new_spanned(
+                        asyncness,
+                        "async fn cannot be registered from an impl block",
+                    ));
+                }
+                let element: Element = content.parse()?;
+                let linkme_path = attr::linkme_path(&mut attrs)?;
+                let element = element.into_method(&qself, &linkme_path);
+                for args in slices {
+                    methods.push((args, element.clone()));
+                }
+            }
+            let item = tokens_between(begin, content.cursor());
+            items.push(quote! {
+                #(#attrs)*
+                #item
+            });
+        }
+
+        Ok(Register {
+            header,
+            inner_attrs,
+            items,
+            methods,
+        })
+    }
+}
+
+// Associated consts, types and macro invocations end with a semicolon, and
+// functions end with their body.
+fn skip_item(input: ParseStream) -> Result<()> {
+    input.step(|cursor| {
+        let mut rest = *cursor;
+        while let Some((tt, next)) = rest.token_tree() {
+            rest = next;
+            match tt {
+                TokenTree::Punct(punct) if punct.as_char() == ';' => return Ok(((), rest)),
+                TokenTree::Group(group) if group.delimiter() == Delimiter::Brace => {
+                    if let Some((punct, next)) = rest.punct() {
+                        if punct.as_char() == ';' {
+                            rest = next;
+                        }
+                    }
+                    return Ok(((), rest));
+                }
+                _ => {}
+            }
+        }
+        Err(cursor.error("expected `;` or `{`"))
+    })
+}
+
+fn tokens_between(begin: Cursor, end: Cursor) -> TokenStream {
+    let mut tokens = TokenStream::new();
+    let mut cursor = begin;
+    while cursor != end {
+        let (tt, next) = cursor.token_tree().unwrap();
+        tokens.extend([tt]);
+        cursor = next;
+    }
+    tokens
+}
+
+pub fn expand(input: Register) -> TokenStream {
+    let header = input.header;
+    let inner_attrs = input.inner_attrs;
+    let items = input.items;
+
+    // The registered functions are named through the impl's self type, so the
+    // elements are emitted outside of the impl block, each in its own scope
+    // since functions of different types may share a name.
+    let elements = input.methods.into_iter().map(|(args, element)| {
+        let expanded = element::expand(args, element);
+        quote! {
+            const _: () = {
+                #expanded
+            };
+        }
+    });
+
+    quote! {
+        #header {
+            #(#inner_attrs)*
+            #(#items)*
+        }
+
+        #(#elements)*
+    }
+}
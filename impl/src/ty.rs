@@ -1,4 +1,23 @@
-use syn::{GenericArgument, Lifetime, PathArguments, Type};
+use proc_macro2::Ident;
+use quote::format_ident;
+use syn::{Expr, ExprLit, GenericArgument, Lifetime, Lit, Path, PathArguments, Type, TypePath};
+
+// Name of the type alias for the element type that a slice or map declaration
+// exports next to the slice, for elements that do not spell out their type.
+// It is derived from the slice's name rather than equal to it so that the
+// slice's name stays free in the type namespace.
+pub(crate) fn element_alias(ident: &Ident) -> Ident {
+    format_ident!("_linkme_element_{}", ident, span = ident.span())
+}
+
+// Path to the element type alias of the slice at the given path.
+pub(crate) fn element_alias_path(path: &Path) -> Type {
+    let mut path = path.clone();
+    if let Some(last) = path.segments.last_mut() {
+        last.ident = element_alias(&last.ident);
+    }
+    Type::Path(TypePath { qself: None, path })
+}
 
 pub(crate) fn populate_static_lifetimes(ty: &mut Type) {
     match ty {
@@ -40,3 +59,99 @@ pub(crate) fn populate_static_lifetimes(ty: &mut Type) {
         _ => unimplemented!("unknown Type"),
     }
 }
+
+// Names every elided lifetime of a reference, and every '_, after the given
+// lifetime. Returns whether there were any.
+pub(crate) fn name_elided_lifetimes(ty: &mut Type, lifetime: &Lifetime) -> bool {
+    let mut named = false;
+    match ty {
+        #![cfg_attr(all(test, exhaustive), deny(non_exhaustive_omitted_patterns))]
+        Type::Array(ty) => named |= name_elided_lifetimes(&mut ty.elem, lifetime),
+        Type::Group(ty) => named |= name_elided_lifetimes(&mut ty.elem, lifetime),
+        Type::Paren(ty) => named |= name_elided_lifetimes(&mut ty.elem, lifetime),
+        Type::Path(ty) => {
+            if let Some(qself) = &mut ty.qself {
+                named |= name_elided_lifetimes(&mut qself.ty, lifetime);
+            }
+            for segment in &mut ty.path.segments {
+                if let PathArguments::AngleBracketed(segment) = &mut segment.arguments {
+                    for arg in &mut segment.args {
+                        match arg {
+                            GenericArgument::Lifetime(arg) if arg.ident == "_" => {
+                                *arg = Lifetime::new(&lifetime.to_string(), arg.apostrophe);
+                                named = true;
+                            }
+                            GenericArgument::Type(arg) => {
+                                named |= name_elided_lifetimes(arg, lifetime);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+        Type::Ptr(ty) => named |= name_elided_lifetimes(&mut ty.elem, lifetime),
+        Type::Reference(ty) => {
+            match &ty.lifetime {
+                None => {
+                    ty.lifetime = Some(Lifetime::new(&lifetime.to_string(), ty.and_token.span));
+                    named = true;
+                }
+                Some(elided) if elided.ident == "_" => {
+                    ty.lifetime = Some(Lifetime::new(&lifetime.to_string(), elided.apostrophe));
+                    named = true;
+                }
+                Some(_) => {}
+            }
+            named |= name_elided_lifetimes(&mut ty.elem, lifetime);
+        }
+        Type::Slice(ty) => named |= name_elided_lifetimes(&mut ty.elem, lifetime),
+        Type::Tuple(ty) => {
+            for elem in &mut ty.elems {
+                named |= name_elided_lifetimes(elem, lifetime);
+            }
+        }
+        Type::ImplTrait(_)
+        | Type::Infer(_)
+        | Type::Macro(_)
+        | Type::Never(_)
+        | Type::TraitObject(_)
+        | Type::BareFn(_)
+        | Type::Verbatim(_) => {}
+
+        _ => unimplemented!("unknown Type"),
+    }
+    named
+}
+
+// Whether the type is recognizably not zero-sized from its syntax alone, in
+// which case an element of the type needs no marker.
+pub(crate) fn is_nonzero_sized(ty: &Type) -> bool {
+    match ty {
+        Type::Array(ty) => {
+            let nonzero_len = match &ty.len {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Int(len), ..
+                }) => len.base10_digits() != "0",
+                _ => false,
+            };
+            nonzero_len && is_nonzero_sized(&ty.elem)
+        }
+        Type::BareFn(_) | Type::Ptr(_) | Type::Reference(_) => true,
+        Type::Group(ty) => is_nonzero_sized(&ty.elem),
+        Type::Paren(ty) => is_nonzero_sized(&ty.elem),
+        Type::Path(ty) => {
+            const PRIMITIVES: [&str; 16] = [
+                "bool", "char", "f32", "f64", "i8", "i16", "i32", "i64", "i128", "isize", "u8",
+                "u16", "u32", "u64", "u128", "usize",
+            ];
+            ty.qself.is_none()
+                && ty
+                    .path
+                    .get_ident()
+                    .is_some_and(|ident| PRIMITIVES.iter().any(|primitive| ident == primitive))
+        }
+        Type::Tuple(ty) => ty.elems.iter().any(is_nonzero_sized),
+        _ => false,
+    }
+}
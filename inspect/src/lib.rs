@@ -0,0 +1,359 @@
+//! List the distributed slices in a compiled binary.
+//!
+//! When an element is missing from a distributed slice at runtime, the linker
+//! sections that linkme places the elements in are the first thing to look
+//! at. This crate reads an ELF, Mach-O, or PE/COFF executable, shared library,
+//! or object file, finds the sections belonging to distributed slices, and
+//! decodes linkme's naming scheme back into the name of each slice, how many
+//! elements and declarations it has, and the symbols of its elements.
+//!
+//! ```no_run
+//! let data = std::fs::read("target/debug/app")?;
+//! for slice in linkme_inspect::inspect(&data)? {
+//!     println!("{}: {:?} elements", slice.name().unwrap_or("?"), slice.len());
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! The same report is printed by the `linkme-inspect` binary:
+//!
+//! ```console
+//! $ linkme-inspect target/debug/app
+//! ```
+//!
+//! # Section names
+//!
+//! On ELF and PE/COFF a slice's sections are named after the slice followed by
+//! a hash, as in `linkme_PLUGINS_O49aQzun`. Mach-O section names are limited to
+//! 16 bytes and hold only the hash, `__linkmeO49aQzun`. The name of such a
+//! slice is instead taken from the symbol of the marker that each declaration
+//! places in the slice's duplicate check section, whose path ends in the name
+//! of the slice. A binary whose symbol table was stripped leaves these slices
+//! unnamed.
+
+#![allow(
+    clippy::missing_errors_doc,
+    clippy::missing_panics_doc,
+    clippy::module_name_repetitions,
+    clippy::must_use_candidate
+)]
+
+mod section;
+
+use crate::section::Role;
+use object::{BinaryFormat, Object, ObjectKind, ObjectSection, ObjectSymbol, SymbolKind};
+use std::collections::btree_map::{BTreeMap, Entry};
+use std::error::Error as StdError;
+use std::fmt::{self, Debug, Display};
+
+/// One distributed slice found in a binary.
+pub struct Slice {
+    name: Option<String>,
+    hash: String,
+    section: Option<String>,
+    len: Option<usize>,
+    stride: Option<u64>,
+    declarations: Option<usize>,
+    symbols: Vec<String>,
+}
+
+/// Error reading a binary.
+pub struct Error(object::Error);
+
+/// List the distributed slices in an executable, shared library, or object
+/// file, ordered by name.
+pub fn inspect(data: &[u8]) -> Result<Vec<Slice>, Error> {
+    let file = object::File::parse(data).map_err(Error)?;
+    let format = file.format();
+    let pointer_width = match file.architecture().address_size() {
+        Some(address_size) => u64::from(address_size.bytes()),
+        None if file.is_64() => 8,
+        None => 4,
+    };
+
+    // In a PE image the grouped sections of a slice are merged, together with
+    // the boundary elements at either end.
+    let boundaries = if format == BinaryFormat::Pe && file.kind() != ObjectKind::Relocatable {
+        2
+    } else {
+        0
+    };
+
+    let mut slices = BTreeMap::new();
+    let mut element_sections = BTreeMap::new();
+    let mut dupcheck_sections = BTreeMap::new();
+    for section in file.sections() {
+        let Ok(name) = section.name() else {
+            continue;
+        };
+        let Some(parsed) = SectionName::parse(name) else {
+            continue;
+        };
+        let builder: &mut Builder = slices.entry(parsed.hash.to_owned()).or_default();
+        if let Some(ident) = parsed.ident {
+            builder.name = Some(ident.to_owned());
+        }
+        match (parsed.role, parsed.part) {
+            (Role::Elements, Part::Elements) => {
+                let whole = name.strip_suffix("$b").unwrap_or(name);
+                builder.section = Some(whole.to_owned());
+                builder.size += section.size();
+                element_sections.insert(section.index().0, parsed.hash.to_owned());
+            }
+            (Role::Elements, Part::Start) => builder.start = Some(section.size()),
+            (Role::Dupcheck, Part::Elements) => {
+                let dupcheck = builder.dupcheck.get_or_insert(0);
+                *dupcheck += section.size();
+                dupcheck_sections.insert(section.index().0, parsed.hash.to_owned());
+            }
+            (Role::Dupcheck | Role::Elements, Part::Start | Part::Stop)
+            | (Role::Info | Role::ForceLink, _) => {}
+        }
+    }
+
+    let mut names = BTreeMap::new();
+    for symbol in file.symbols() {
+        let Ok(name) = symbol.name() else {
+            continue;
+        };
+        // Mach-O prefixes symbols with an underscore.
+        let name = if format == BinaryFormat::MachO {
+            name.strip_prefix('_').unwrap_or(name)
+        } else {
+            name
+        };
+        let Some(index) = symbol.section_index() else {
+            continue;
+        };
+        if matches!(symbol.kind(), SymbolKind::Section | SymbolKind::File)
+            || name.starts_with("__start_")
+            || name.starts_with("__stop_")
+        {
+            continue;
+        }
+        if let Some(hash) = dupcheck_sections.get(&index.0) {
+            if let Some(ident) = ident_from_marker(&demangle(name)) {
+                names.insert(hash.clone(), ident.to_owned());
+            }
+            continue;
+        }
+        let Some(hash) = element_sections.get(&index.0) else {
+            continue;
+        };
+        if format == BinaryFormat::Elf && symbol.size() == 0 {
+            continue;
+        }
+        let size = (format == BinaryFormat::Elf).then(|| symbol.size());
+        let builder = slices.get_mut(hash).unwrap();
+        builder
+            .symbols
+            .push((symbol.address(), size, demangle(name)));
+    }
+
+    for (hash, name) in names {
+        if let Entry::Occupied(mut entry) = slices.entry(hash) {
+            entry.get_mut().name.get_or_insert(name);
+        }
+    }
+
+    let mut slices: Vec<Slice> = slices
+        .into_iter()
+        .map(|(hash, builder)| builder.build(hash, pointer_width, boundaries))
+        .collect();
+    slices.sort_by(|a, b| {
+        (a.name.is_none(), &a.name, &a.hash).cmp(&(b.name.is_none(), &b.name, &b.hash))
+    });
+    Ok(slices)
+}
+
+impl Slice {
+    /// Name of the slice, if it could be decoded.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The 8-character hash distinguishing the slice's sections from those of
+    /// a slice of the same name declared in a different crate.
+    pub fn hash(&self) -> &str {
+        &self.hash
+    }
+
+    /// Name of the section holding the elements, unless the binary has none.
+    pub fn section(&self) -> Option<&str> {
+        self.section.as_deref()
+    }
+
+    /// Number of elements, if it can be told from the binary.
+    pub fn len(&self) -> Option<usize> {
+        self.len
+    }
+
+    /// Whether the slice is known to have no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len == Some(0)
+    }
+
+    /// Distance in bytes between consecutive elements, if it can be told from
+    /// the binary.
+    pub fn stride(&self) -> Option<u64> {
+        self.stride
+    }
+
+    /// Number of declarations of the slice linked into the binary. More than
+    /// one is the duplicate declaration that linkme reports at runtime.
+    pub fn declarations(&self) -> Option<usize> {
+        self.declarations
+    }
+
+    /// Demangled symbol names of the elements, in section order. Empty if the
+    /// binary's symbol table was stripped.
+    pub fn symbols(&self) -> &[String] {
+        &self.symbols
+    }
+}
+
+impl Debug for Slice {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("Slice")
+            .field("name", &self.name)
+            .field("hash", &self.hash)
+            .field("section", &self.section)
+            .field("len", &self.len)
+            .field("stride", &self.stride)
+            .field("declarations", &self.declarations)
+            .field("symbols", &self.symbols)
+            .finish()
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.0, formatter)
+    }
+}
+
+impl Debug for Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(&self.0, formatter)
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&self.0)
+    }
+}
+
+#[derive(Default)]
+struct Builder {
+    name: Option<String>,
+    section: Option<String>,
+    size: u64,
+    // Size of the start boundary element of a COFF object, which is one
+    // element of the slice's type.
+    start: Option<u64>,
+    dupcheck: Option<u64>,
+    symbols: Vec<(u64, Option<u64>, String)>,
+}
+
+impl Builder {
+    fn build(mut self, hash: String, pointer_width: u64, boundaries: u64) -> Slice {
+        self.symbols.sort_by_key(|&(address, _size, _)| address);
+
+        // An ELF symbol carries the size of the element it names. If all have
+        // the same size and together fill the section, that is the stride.
+        let symbol_stride = match self.symbols.first() {
+            Some(&(_, Some(size), _))
+                if self.symbols.iter().all(|symbol| symbol.1 == Some(size))
+                    && size * self.symbols.len() as u64 == self.size =>
+            {
+                Some(size)
+            }
+            _ => None,
+        };
+        let stride = self.start.filter(|&size| size > 0).or(symbol_stride);
+
+        let len = if self.section.is_none() {
+            None
+        } else if let Some(stride) = stride {
+            let len = (self.size / stride).saturating_sub(boundaries);
+            usize::try_from(len).ok()
+        } else if !self.symbols.is_empty() {
+            Some(self.symbols.len())
+        } else if self.size == 0 {
+            Some(0)
+        } else {
+            None
+        };
+
+        let declarations = self.dupcheck.and_then(|size| {
+            let declarations = (size / pointer_width).saturating_sub(boundaries);
+            usize::try_from(declarations).ok()
+        });
+
+        Slice {
+            name: self.name,
+            hash,
+            section: self.section,
+            len,
+            stride,
+            declarations,
+            symbols: self
+                .symbols
+                .into_iter()
+                .map(|(_address, _size, name)| name)
+                .collect(),
+        }
+    }
+}
+
+// Grouped sections of PE/COFF, whose linker sorts `$a` before `$b` before
+// `$c`. Sections of other formats hold only elements.
+#[derive(Copy, Clone)]
+enum Part {
+    Start,
+    Elements,
+    Stop,
+}
+
+struct SectionName<'a> {
+    role: Role,
+    part: Part,
+    ident: Option<&'a str>,
+    hash: &'a str,
+}
+
+impl<'a> SectionName<'a> {
+    fn parse(name: &'a str) -> Option<Self> {
+        let (name, part) = if let Some(name) = name.strip_suffix("$a") {
+            (name, Part::Start)
+        } else if let Some(name) = name.strip_suffix("$b") {
+            (name, Part::Elements)
+        } else if let Some(name) = name.strip_suffix("$c") {
+            (name, Part::Stop)
+        } else {
+            (name, Part::Elements)
+        };
+        let (role, ident, hash) = section::parse(name)?;
+        Some(SectionName {
+            role,
+            part,
+            ident,
+            hash,
+        })
+    }
+}
+
+// The duplicate check marker is a static named DUPCHECK inside the initializer
+// of the slice's own static, so its path is that of the slice followed by
+// `::DUPCHECK`.
+fn ident_from_marker(path: &str) -> Option<&str> {
+    let path = path.strip_suffix("::DUPCHECK")?;
+    let ident = path.rsplit("::").next()?;
+    (!ident.is_empty()).then_some(ident)
+}
+
+fn demangle(name: &str) -> String {
+    format!("{:#}", rustc_demangle::demangle(name))
+}
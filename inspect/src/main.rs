@@ -0,0 +1,90 @@
+#![allow(clippy::uninlined_format_args)]
+
+use linkme_inspect::Slice;
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process;
+
+const USAGE: &str = "\
+Usage: linkme-inspect FILE...
+
+List the distributed slices in ELF, Mach-O, and PE/COFF executables, shared
+libraries, and object files.
+";
+
+fn main() {
+    let mut paths = Vec::new();
+    for arg in env::args_os().skip(1) {
+        if arg == "--help" || arg == "-h" {
+            print!("{}", USAGE);
+            return;
+        }
+        paths.push(PathBuf::from(arg));
+    }
+    if paths.is_empty() {
+        usage_error("no file given");
+    }
+
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+    let mut failed = false;
+    for (i, path) in paths.iter().enumerate() {
+        let report = fs::read(path)
+            .map_err(|err| err.to_string())
+            .and_then(|data| linkme_inspect::inspect(&data).map_err(|err| err.to_string()));
+        let slices = match report {
+            Ok(slices) => slices,
+            Err(err) => {
+                let _ = stdout.flush();
+                eprintln!("linkme-inspect: {}: {}", path.display(), err);
+                failed = true;
+                continue;
+            }
+        };
+        if paths.len() > 1 {
+            if i > 0 {
+                let _ = writeln!(stdout);
+            }
+            let _ = writeln!(stdout, "{}:", path.display());
+        }
+        if slices.is_empty() {
+            let _ = writeln!(stdout, "no distributed slices");
+        }
+        for slice in &slices {
+            let _ = print(&mut stdout, slice);
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+fn print(out: &mut dyn Write, slice: &Slice) -> io::Result<()> {
+    writeln!(out, "{}", slice.name().unwrap_or("<unknown>"))?;
+    writeln!(out, "    hash: {}", slice.hash())?;
+    if let Some(section) = slice.section() {
+        writeln!(out, "    section: {}", section)?;
+    }
+    writeln!(out, "    elements: {}", or_unknown(slice.len()))?;
+    writeln!(out, "    stride: {}", or_unknown(slice.stride()))?;
+    writeln!(
+        out,
+        "    declarations: {}",
+        or_unknown(slice.declarations())
+    )?;
+    for symbol in slice.symbols() {
+        writeln!(out, "    - {}", symbol)?;
+    }
+    Ok(())
+}
+
+fn or_unknown(value: Option<impl ToString>) -> String {
+    value.map_or_else(|| "unknown".to_owned(), |value| value.to_string())
+}
+
+fn usage_error(msg: &str) -> ! {
+    eprintln!("linkme-inspect: {}\n\n{}", msg, USAGE);
+    process::exit(2);
+}
@@ -0,0 +1,192 @@
+// Names of the linker sections and symbols belonging to a distributed slice.
+//
+// This file is shared by linkme-impl, which emits the names, linkme's build
+// module, which writes them into C headers and linker scripts, and
+// linkme-inspect, which recognizes them in a binary. Each of the other two
+// crates packages its own copy of impl/src/section.rs, which CI checks to be
+// identical to it. Not all of them use every item.
+
+#![allow(dead_code)]
+
+use std::collections::hash_map;
+use std::fmt::{self, Display, Write as _};
+use std::format;
+use std::hash::{Hash as _, Hasher as _};
+use std::string::String;
+
+// 8-character symbol hash consisting of a-zA-Z0-9. We use 8 character because
+// Mach-O section specifiers are restricted to at most 16 characters (see
+// https://github.com/dtolnay/linkme/issues/35) and we leave room for a
+// linkme-specific prefix.
+pub(crate) struct Hash(u64);
+
+// Identifiers need not be unique beyond the declaring crate because the
+// #[macro_export] macro of a declaration already requires the identifier to be
+// unique within its crate. Including the version keeps apart two
+// semver-incompatible copies of one crate in the same dependency graph, and
+// the package name keeps apart two packages whose library crates share a name
+// and version. Two builds of one package with different features still share
+// a hash, so that their declarations are caught as duplicates, and so do two
+// copies of one package from different sources.
+//
+// Nothing here depends on where the package is on disk, so section names are
+// the same across checkouts and vendored copies of the same source. The
+// standard library's hasher is only stable within one compiler, which is
+// enough because the proc macro and a build script of the same build always
+// agree.
+pub(crate) fn hash(krate: &str, version: &str, package: &str, ident: &str) -> Hash {
+    let mut hasher = hash_map::DefaultHasher::new();
+    format!("{krate}@{version}").hash(&mut hasher);
+    package.hash(&mut hasher);
+    ident.hash(&mut hasher);
+    Hash(hasher.finish())
+}
+
+impl Display for Hash {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        // log(62^8)/log(2) is 47.6 so we have enough bits in the 64-bit
+        // standard library hash to produce a good distribution over 8 digits
+        // from a 62-character alphabet.
+        let mut remainder = self.0;
+        for _ in 0..8 {
+            let digit = (remainder % 62) as u8;
+            remainder /= 62;
+            formatter.write_char(match digit {
+                0..=25 => b'a' + digit,
+                26..=51 => b'A' + digit - 26,
+                52..=61 => b'0' + digit - 52,
+                _ => unreachable!(),
+            } as char)?;
+        }
+        Ok(())
+    }
+}
+
+// A slice has a section holding its elements, one into which every
+// declaration of the slice places a marker for the duplicate check, one
+// holding the ElementInfo of each element under #[linkme(element_info)], and
+// one holding the crates that force_link! expects to contribute to a slice
+// under #[linkme(force_link)].
+#[derive(Copy, Clone, PartialEq)]
+pub(crate) enum Role {
+    Elements,
+    Dupcheck,
+    Info,
+    ForceLink,
+}
+
+impl Role {
+    pub(crate) const ALL: [Self; 4] = [Role::Elements, Role::Dupcheck, Role::Info, Role::ForceLink];
+
+    fn prefix(self) -> &'static str {
+        match self {
+            Role::Elements => "linkme",
+            Role::Dupcheck => "linkm2",
+            Role::Info => "linkm3",
+            Role::ForceLink => "linkm4",
+        }
+    }
+}
+
+// ELF other than illumos: linkme_{IDENT}_{HASH}
+pub(crate) fn elf(role: Role, ident: &str, hash: &Hash) -> String {
+    format!("{}_{ident}_{hash}", role.prefix())
+}
+
+// illumos: set_linkme_{IDENT}_{HASH}
+pub(crate) fn illumos(role: Role, ident: &str, hash: &Hash) -> String {
+    format!("set_{}", elf(role, ident, hash))
+}
+
+// Mach-O, within the __DATA segment: __linkme{HASH}
+pub(crate) fn macho(role: Role, hash: &Hash) -> String {
+    format!("__{}{hash}", role.prefix())
+}
+
+// PE/COFF, without the `$a`, `$b`, or `$c` suffix ordering the grouped
+// sections: .linkme_{IDENT}_{HASH}
+pub(crate) fn windows(role: Role, ident: &str, hash: &Hash) -> String {
+    format!(".{}", elf(role, ident, hash))
+}
+
+// The role, identifier, and hash of a section named by any of the above.
+// Mach-O section names have no room for the identifier.
+pub(crate) fn parse(name: &str) -> Option<(Role, Option<&str>, &str)> {
+    if let Some(name) = name.strip_prefix("__") {
+        let (role, hash) = strip_role(name)?;
+        return is_hash(hash).then_some((role, None, hash));
+    }
+
+    let name = name
+        .strip_prefix('.')
+        .or_else(|| name.strip_prefix("set_"))
+        .unwrap_or(name);
+    let (role, rest) = strip_role(name)?;
+    let (ident, hash) = rest.strip_prefix('_')?.rsplit_once('_')?;
+    (!ident.is_empty() && is_hash(hash)).then_some((role, Some(ident), hash))
+}
+
+fn strip_role(name: &str) -> Option<(Role, &str)> {
+    Role::ALL
+        .into_iter()
+        .find_map(|role| Some((role, name.strip_prefix(role.prefix())?)))
+}
+
+fn is_hash(string: &str) -> bool {
+    string.len() == 8 && string.bytes().all(|byte| byte.is_ascii_alphanumeric())
+}
+
+// Symbol names, as opposed to section names, are uniform across platforms.
+
+pub(crate) fn duplicate_symbol(ident: &str, hash: &Hash) -> String {
+    format!("linkme_duplicate_distributed_slice_{ident}_{hash}")
+}
+
+pub(crate) fn bounds_symbol(ident: &str, hash: &Hash) -> String {
+    format!("linkme_distributed_slice_bounds_{ident}_{hash}")
+}
+
+pub(crate) fn nonempty_symbol(ident: &str, hash: &Hash) -> String {
+    format!("linkme_empty_distributed_slice_{ident}_{hash}")
+}
+
+pub(crate) fn too_many_symbol(ident: &str, hash: &Hash) -> String {
+    format!("linkme_too_many_elements_distributed_slice_{ident}_{hash}")
+}
+
+pub(crate) fn slot_symbol(ident: &str, hash: &Hash) -> String {
+    format!("linkme_distributed_slot_{ident}_{hash}")
+}
+
+pub(crate) fn slot_override_symbol(ident: &str, hash: &Hash) -> String {
+    format!("linkme_distributed_slot_override_{ident}_{hash}")
+}
+
+#[test]
+fn test_hash() {
+    use std::string::ToString as _;
+
+    let hash = |version, package| hash("example", version, package, "EXAMPLE").to_string();
+    assert_eq!(hash("1.0.0", "example"), "DBxJOJ9t");
+    assert_ne!(hash("1.0.0", "example"), hash("2.0.0", "example"));
+    assert_ne!(hash("1.0.0", "example"), hash("1.0.0", "example-fork"));
+}
+
+#[test]
+fn test_parse() {
+    let hash = hash("example", "1.0.0", "example", "EXAMPLE");
+    for role in Role::ALL {
+        let names = [
+            (elf(role, "EXAMPLE", &hash), Some("EXAMPLE")),
+            (illumos(role, "EXAMPLE", &hash), Some("EXAMPLE")),
+            (windows(role, "EXAMPLE", &hash), Some("EXAMPLE")),
+            (macho(role, &hash), None),
+        ];
+        for (name, ident) in &names {
+            let parsed = parse(name).unwrap();
+            assert!(parsed.0 == role);
+            assert_eq!(parsed.1, *ident);
+            assert_eq!(parsed.2, "DBxJOJ9t");
+        }
+    }
+}
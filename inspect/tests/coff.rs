@@ -0,0 +1,56 @@
+use object::write::{Object, SectionId, Symbol, SymbolSection};
+use object::{
+    Architecture, BinaryFormat, Endianness, SectionKind, SymbolFlags, SymbolKind, SymbolScope,
+};
+
+fn section(object: &mut Object, name: &str, len: usize) -> (SectionId, u64) {
+    let section = object.add_section(Vec::new(), name.as_bytes().to_vec(), SectionKind::Data);
+    let offset = object.append_section_data(section, &vec![0; len], 8);
+    (section, offset)
+}
+
+// Sections as emitted for `pub static EXAMPLE: [[u64; 2]]` declared in
+// example 1.0.0, with boundary elements in `$a` and `$c`, two elements, and
+// two declarations.
+fn fixture() -> Vec<u8> {
+    let mut object = Object::new(BinaryFormat::Coff, Architecture::X86_64, Endianness::Little);
+
+    section(&mut object, ".linkme_EXAMPLE_O49aQzun$a", 16);
+    for name in ["JSON", "YAML"] {
+        let (section, offset) = section(&mut object, ".linkme_EXAMPLE_O49aQzun$b", 16);
+        object.add_symbol(Symbol {
+            name: name.as_bytes().to_vec(),
+            value: offset,
+            size: 16,
+            kind: SymbolKind::Data,
+            scope: SymbolScope::Compilation,
+            weak: false,
+            section: SymbolSection::Section(section),
+            flags: SymbolFlags::None,
+        });
+    }
+    section(&mut object, ".linkme_EXAMPLE_O49aQzun$c", 16);
+
+    section(&mut object, ".linkm2_EXAMPLE_O49aQzun$a", 8);
+    section(&mut object, ".linkm2_EXAMPLE_O49aQzun$b", 8);
+    section(&mut object, ".linkm2_EXAMPLE_O49aQzun$b", 8);
+    section(&mut object, ".linkm2_EXAMPLE_O49aQzun$c", 8);
+
+    object.write().unwrap()
+}
+
+#[test]
+fn test_object() {
+    let data = fixture();
+    let slices = linkme_inspect::inspect(&data).unwrap();
+    assert_eq!(slices.len(), 1);
+
+    let example = &slices[0];
+    assert_eq!(example.name(), Some("EXAMPLE"));
+    assert_eq!(example.hash(), "O49aQzun");
+    assert_eq!(example.section(), Some(".linkme_EXAMPLE_O49aQzun"));
+    assert_eq!(example.len(), Some(2));
+    assert_eq!(example.stride(), Some(16));
+    assert_eq!(example.declarations(), Some(2));
+    assert_eq!(example.symbols(), ["JSON", "YAML"]);
+}
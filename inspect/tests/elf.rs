@@ -0,0 +1,47 @@
+#![cfg(target_os = "linux")]
+
+use linkme::distributed_slice;
+use std::env;
+use std::fs;
+
+#[distributed_slice]
+pub static NUMBERS: [u64];
+
+#[distributed_slice(NUMBERS)]
+static ONE: u64 = 1;
+
+#[distributed_slice(NUMBERS)]
+static TWO: u64 = 2;
+
+#[distributed_slice]
+pub static EMPTY: [u32];
+
+#[test]
+fn test_current_exe() {
+    assert_eq!(NUMBERS.len(), 2);
+    assert!(EMPTY.is_empty());
+
+    let data = fs::read(env::current_exe().unwrap()).unwrap();
+    let slices = linkme_inspect::inspect(&data).unwrap();
+
+    let numbers = slices
+        .iter()
+        .find(|slice| slice.name() == Some("NUMBERS"))
+        .unwrap();
+    let section = format!("linkme_NUMBERS_{}", numbers.hash());
+    assert_eq!(numbers.section(), Some(section.as_str()));
+    assert_eq!(numbers.len(), Some(2));
+    assert_eq!(numbers.stride(), Some(8));
+    assert_eq!(numbers.declarations(), Some(1));
+    let mut symbols = numbers.symbols().to_vec();
+    symbols.sort();
+    assert_eq!(symbols, ["elf::ONE", "elf::TWO"]);
+
+    let empty = slices
+        .iter()
+        .find(|slice| slice.name() == Some("EMPTY"))
+        .unwrap();
+    assert!(empty.is_empty());
+    assert_eq!(empty.declarations(), Some(1));
+    assert!(empty.symbols().is_empty());
+}
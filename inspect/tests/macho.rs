@@ -0,0 +1,83 @@
+use object::write::{Object, Symbol, SymbolSection};
+use object::{
+    Architecture, BinaryFormat, Endianness, SectionKind, SymbolFlags, SymbolKind, SymbolScope,
+};
+
+// Sections as emitted for `pub static EXAMPLE: [[u64; 2]]` declared in
+// example 1.0.0 with two elements, and for a slice ROUTES whose declaration's
+// marker has no symbol, as in a stripped binary.
+fn fixture() -> Vec<u8> {
+    let mut object = Object::new(
+        BinaryFormat::MachO,
+        Architecture::Aarch64,
+        Endianness::Little,
+    );
+
+    let elements = object.add_section(
+        b"__DATA".to_vec(),
+        b"__linkmeO49aQzun".to_vec(),
+        SectionKind::Data,
+    );
+    for name in ["JSON", "YAML"] {
+        let offset = object.append_section_data(elements, &[0; 16], 8);
+        object.add_symbol(Symbol {
+            name: name.as_bytes().to_vec(),
+            value: offset,
+            size: 16,
+            kind: SymbolKind::Data,
+            scope: SymbolScope::Compilation,
+            weak: false,
+            section: SymbolSection::Section(elements),
+            flags: SymbolFlags::None,
+        });
+    }
+
+    let dupcheck = object.add_section(
+        b"__DATA".to_vec(),
+        b"__linkm2O49aQzun".to_vec(),
+        SectionKind::Data,
+    );
+    let offset = object.append_section_data(dupcheck, &1u64.to_le_bytes(), 8);
+    object.add_symbol(Symbol {
+        name: b"_ZN7example7EXAMPLE8DUPCHECK17h0123456789abcdefE".to_vec(),
+        value: offset,
+        size: 8,
+        kind: SymbolKind::Data,
+        scope: SymbolScope::Compilation,
+        weak: false,
+        section: SymbolSection::Section(dupcheck),
+        flags: SymbolFlags::None,
+    });
+
+    let routes = object.add_section(
+        b"__DATA".to_vec(),
+        b"__linkm2abcdEFGH".to_vec(),
+        SectionKind::Data,
+    );
+    object.append_section_data(routes, &1u64.to_le_bytes(), 8);
+
+    object.write().unwrap()
+}
+
+#[test]
+fn test_name_from_symbol() {
+    let data = fixture();
+    let slices = linkme_inspect::inspect(&data).unwrap();
+    assert_eq!(slices.len(), 2);
+
+    let example = &slices[0];
+    assert_eq!(example.name(), Some("EXAMPLE"));
+    assert_eq!(example.hash(), "O49aQzun");
+    assert_eq!(example.section(), Some("__linkmeO49aQzun"));
+    assert_eq!(example.len(), Some(2));
+    assert_eq!(example.stride(), None);
+    assert_eq!(example.declarations(), Some(1));
+    assert_eq!(example.symbols(), ["JSON", "YAML"]);
+
+    let routes = &slices[1];
+    assert_eq!(routes.name(), None);
+    assert_eq!(routes.hash(), "abcdEFGH");
+    assert_eq!(routes.section(), None);
+    assert_eq!(routes.len(), None);
+    assert_eq!(routes.declarations(), Some(1));
+}
@@ -0,0 +1,416 @@
+//! Build script support for contributing elements from C and C++, and for
+//! linker scripts of embedded targets.
+//!
+//! # C and C++
+//!
+//! A C header generated by [`CHeader`] lets C or C++ code compiled by the
+//! build script add elements to a distributed slice declared in Rust. For
+//! every slice, the header asserts that the C element type has the size and
+//! alignment of the Rust element type, and defines a macro that places an
+//! element into the slice's linker section on each supported platform.
+//!
+//! ```no_run
+//! // build.rs
+//!
+//! fn main() {
+//!     let out_dir = std::path::PathBuf::from(std::env::var_os("OUT_DIR").unwrap());
+//!
+//!     linkme::build::CHeader::new()
+//!         .slice(linkme::build::Slice::new("COMMANDS", "struct command", 16, 8))
+//!         .write_to_file(out_dir.join("linkme.h"))
+//!         .unwrap();
+//!
+//!     cc::Build::new()
+//!         .file("src/commands.c")
+//!         .include(&out_dir)
+//!         .link_lib_modifier("+whole-archive")
+//!         .compile("commands");
+//! }
+//! # mod cc {
+//! #     pub struct Build;
+//! #     impl Build {
+//! #         pub fn new() -> Self { Build }
+//! #         pub fn file(&mut self, _: &str) -> &mut Self { self }
+//! #         pub fn include(&mut self, _: &std::path::Path) -> &mut Self { self }
+//! #         pub fn link_lib_modifier(&mut self, _: &str) -> &mut Self { self }
+//! #         pub fn compile(&mut self, _: &str) {}
+//! #     }
+//! # }
+//! ```
+//!
+//! ```c
+//! // src/commands.c
+//!
+//! #include "linkme.h"
+//!
+//! struct command {
+//!     const char *name;
+//!     int (*run)(void);
+//! };
+//!
+//! static int run_status(void) { /* ... */ return 0; }
+//!
+//! LINKME_ELEMENT(COMMANDS, STATUS) = {"status", run_status};
+//! ```
+//!
+//! The elements are picked up by nothing but the section they are placed in,
+//! so a static library holding them must be linked with `+whole-archive` for
+//! the linker to include it.
+//!
+//! # Linker scripts
+//!
+//! On `target_os = "none"`, the program's linker script decides where sections
+//! end up. A script that does not mention linkme's sections may let
+//! `--gc-sections` discard the elements, or place them in RAM where nothing
+//! initializes them. A script fragment generated by [`LinkerScript`] keeps the
+//! sections of the listed slices and places them after `.rodata` in flash.
+//!
+//! ```no_run
+//! // build.rs, fn main
+//!
+//! let out_dir = std::path::PathBuf::from(std::env::var_os("OUT_DIR").unwrap());
+//!
+//! linkme::build::LinkerScript::new()
+//!     .slice("COMMANDS")
+//!     .write_to_file(out_dir.join("linkme.x"))
+//!     .unwrap();
+//!
+//! println!("cargo:rustc-link-search={}", out_dir.display());
+//! println!("cargo:rustc-link-arg=-Tlinkme.x");
+//! ```
+
+mod section;
+
+use self::section::{Hash, Role};
+use std::env;
+use std::fmt::Write as _;
+use std::format;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+/// Generator of a C header for distributed slices declared by one crate.
+pub struct CHeader {
+    krate: String,
+    version: String,
+    package: String,
+    slices: Vec<Slice>,
+}
+
+/// Generator of a linker script fragment for distributed slices declared by
+/// one crate, for GNU ld and LLD.
+pub struct LinkerScript {
+    krate: String,
+    version: String,
+    package: String,
+    region: String,
+    insert_after: String,
+    pointer_width: usize,
+    slices: Vec<(String, Option<String>)>,
+}
+
+/// Description of one distributed slice for [`CHeader`].
+pub struct Slice {
+    name: String,
+    element_type: String,
+    size: usize,
+    align: usize,
+    section: Option<String>,
+}
+
+impl CHeader {
+    /// Header for distributed slices declared by the library of the package
+    /// whose build script is running.
+    pub fn new() -> Self {
+        let krate = env::var("CARGO_PKG_NAME").unwrap_or_default();
+        CHeader {
+            krate: krate.replace('-', "_"),
+            version: env::var("CARGO_PKG_VERSION").unwrap_or_default(),
+            package: krate,
+            slices: Vec::new(),
+        }
+    }
+
+    /// Name of the crate declaring the slices, if not the package's library,
+    /// for example `"my_test"` for slices declared in `tests/my_test.rs`.
+    pub fn krate(&mut self, name: &str) -> &mut Self {
+        self.krate = name.to_string();
+        self
+    }
+
+    /// Add a slice to the header.
+    pub fn slice(&mut self, slice: Slice) -> &mut Self {
+        self.slices.push(slice);
+        self
+    }
+
+    /// Contents of the header.
+    pub fn generate(&self) -> String {
+        let guard = format!("LINKME_{}_H", self.krate.to_ascii_uppercase());
+        let mut header = String::new();
+        header.push_str("/* Generated by linkme. Do not edit. */\n\n");
+        let _ = writeln!(header, "#ifndef {guard}");
+        let _ = writeln!(header, "#define {guard}");
+        header.push_str(PRELUDE);
+
+        for slice in &self.slices {
+            let name = &slice.name;
+            let ty = &slice.element_type;
+            let hash = section::hash(&self.krate, &self.version, &self.package, name);
+            let sections = slice.sections(&hash);
+            // Defined by every element, for the link-time check of a slice
+            // declared with `min` or `exact`.
+            let nonempty = section::nonempty_symbol(name, &hash);
+            let _ = write!(
+                header,
+                "\n\
+                 /* {name}: {ty}, {size} bytes, aligned to {align} */\n\
+                 LINKME_STATIC_ASSERT(sizeof({ty}) == {size}, \"size of {ty} does not match element type of {name}\");\n\
+                 LINKME_STATIC_ASSERT(LINKME_ALIGNOF({ty}) == {align}, \"alignment of {ty} does not match element type of {name}\");\n\
+                 #if defined(_MSC_VER) && !defined(__clang__)\n\
+                 #define LINKME_ELEMENT_{name}(name) __pragma(section(\"{windows}\", read)) __declspec(allocate(\"{windows}\")) LINKME_MSVC_EXTERN const {ty} name\n\
+                 #else\n\
+                 #if defined(__APPLE__)\n\
+                 #define LINKME_SECTION_{name} \"{macho}\"\n\
+                 #elif defined(_WIN32)\n\
+                 #define LINKME_SECTION_{name} \"{windows}\"\n\
+                 #elif defined(__illumos__) || defined(__sun)\n\
+                 #define LINKME_SECTION_{name} \"{illumos}\"\n\
+                 #elif defined(__FreeBSD__) || defined(__OpenBSD__)\n\
+                 #define LINKME_SECTION_{name} \"{bsd}\"\n\
+                 #else\n\
+                 #define LINKME_SECTION_{name} \"{linux}\"\n\
+                 #endif\n\
+                 #if defined(__ELF__)\n\
+                 #define LINKME_NONEMPTY_{name} __asm__(\".ifndef {nonempty}\\n.pushsection .rodata.{nonempty},\\\"a\\\"\\n.weak {nonempty}\\n{nonempty}:\\n.byte 0\\n.popsection\\n.endif\");\n\
+                 #else\n\
+                 #define LINKME_NONEMPTY_{name}\n\
+                 #endif\n\
+                 #define LINKME_ELEMENT_{name}(name) LINKME_NONEMPTY_{name} LINKME_USED __attribute__((section(LINKME_SECTION_{name}))) static const {ty} name\n\
+                 #endif\n",
+                size = slice.size,
+                align = slice.align,
+                linux = sections.linux,
+                macho = sections.macho,
+                windows = sections.windows,
+                illumos = sections.illumos,
+                bsd = sections.bsd,
+            );
+        }
+
+        let _ = writeln!(header, "\n#endif /* {guard} */");
+        header
+    }
+
+    /// Write the header to a file, typically in `OUT_DIR`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from writing the file.
+    pub fn write_to_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.generate())
+    }
+}
+
+impl Default for CHeader {
+    fn default() -> Self {
+        CHeader::new()
+    }
+}
+
+impl LinkerScript {
+    /// Script for distributed slices declared by the library of the package
+    /// whose build script is running, placed after `.rodata` in the memory
+    /// region `FLASH`.
+    pub fn new() -> Self {
+        let krate = env::var("CARGO_PKG_NAME").unwrap_or_default();
+        LinkerScript {
+            krate: krate.replace('-', "_"),
+            version: env::var("CARGO_PKG_VERSION").unwrap_or_default(),
+            package: krate,
+            region: "FLASH".to_string(),
+            insert_after: ".rodata".to_string(),
+            pointer_width: env::var("CARGO_CFG_TARGET_POINTER_WIDTH")
+                .ok()
+                .and_then(|bits| bits.parse::<usize>().ok())
+                .map_or(4, |bits| bits / 8),
+            slices: Vec::new(),
+        }
+    }
+
+    /// Name of the crate declaring the slices, if not named after the package,
+    /// for example `"firmware"` for slices declared in a binary target named
+    /// `firmware`.
+    pub fn krate(&mut self, name: &str) -> &mut Self {
+        self.krate = name.to_string();
+        self
+    }
+
+    /// Memory region holding the sections, as named in the `MEMORY` command of
+    /// the program's linker script.
+    pub fn region(&mut self, region: &str) -> &mut Self {
+        self.region = region.to_string();
+        self
+    }
+
+    /// Output section after which the sections are inserted.
+    pub fn insert_after(&mut self, section: &str) -> &mut Self {
+        self.insert_after = section.to_string();
+        self
+    }
+
+    /// Add a distributed slice, map, or slot by the name of its declaration.
+    pub fn slice(&mut self, name: &str) -> &mut Self {
+        self.slices.push((name.to_string(), None));
+        self
+    }
+
+    /// Add a distributed slice whose declaration names its section by
+    /// `#[linkme(section = "...")]`.
+    pub fn slice_in_section(&mut self, name: &str, section: &str) -> &mut Self {
+        self.slices
+            .push((name.to_string(), Some(section.to_string())));
+        self
+    }
+
+    /// Contents of the script.
+    ///
+    /// Each slice's elements, the marker that detects duplicate declarations,
+    /// the information recorded by `#[linkme(element_info)]`, and the crates
+    /// that `force_link!` expects to contribute each get an output section
+    /// which is kept whole and bounded by the `__start_` and `__stop_` symbols
+    /// that the declaration refers to. An `ASSERT` fails the link if more than
+    /// one declaration of a slice is linked, which would otherwise only be
+    /// reported by a panic at the first access to the slice.
+    pub fn generate(&self) -> String {
+        let mut script = String::new();
+        script.push_str("/* Generated by linkme. Do not edit. */\n\nSECTIONS\n{\n");
+        for (name, custom_section) in &self.slices {
+            let hash = section::hash(&self.krate, &self.version, &self.package, name);
+            let _ = writeln!(script, "  /* {name} */");
+            for role in Role::ALL {
+                let section = match custom_section {
+                    Some(custom_section) if role == Role::Elements => custom_section.clone(),
+                    _ => section::elf(role, name, &hash),
+                };
+                let _ = write!(
+                    script,
+                    "  {section} :\n  \
+                     {{\n    \
+                     PROVIDE(__start_{section} = .);\n    \
+                     KEEP(*({section}))\n    \
+                     PROVIDE(__stop_{section} = .);\n  \
+                     }} > {region}\n",
+                    region = self.region,
+                );
+            }
+            let _ = writeln!(
+                script,
+                "  ASSERT(SIZEOF({dupcheck}) <= {pointer_width}, \"duplicate #[distributed_slice] with name {name}\")",
+                dupcheck = section::elf(Role::Dupcheck, name, &hash),
+                pointer_width = self.pointer_width,
+            );
+        }
+        let _ = writeln!(script, "}}\nINSERT AFTER {};", self.insert_after);
+        script
+    }
+
+    /// Write the script to a file, typically in `OUT_DIR`, which is then
+    /// passed to the linker by `cargo:rustc-link-arg=-T...`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error from writing the file.
+    pub fn write_to_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.generate())
+    }
+}
+
+impl Default for LinkerScript {
+    fn default() -> Self {
+        LinkerScript::new()
+    }
+}
+
+impl Slice {
+    /// A slice declared as `static NAME: [T]`, whose elements are written in
+    /// C as `element_type`, where `size` and `align` are those of `T`.
+    ///
+    /// The element type must be usable as `element_type name`, so a function
+    /// pointer type needs a typedef.
+    pub fn new(name: &str, element_type: &str, size: usize, align: usize) -> Self {
+        Slice {
+            name: name.to_string(),
+            element_type: element_type.to_string(),
+            size,
+            align,
+            section: None,
+        }
+    }
+
+    /// Section name given by `#[linkme(section = "...")]` on the declaration.
+    #[must_use]
+    pub fn section(mut self, section: &str) -> Self {
+        self.section = Some(section.to_string());
+        self
+    }
+
+    fn sections(&self, hash: &Hash) -> Sections {
+        if let Some(section) = &self.section {
+            return Sections {
+                linux: section.clone(),
+                macho: format!("__DATA,{section},regular,no_dead_strip"),
+                windows: format!("{section}$b"),
+                illumos: section.clone(),
+                bsd: section.clone(),
+            };
+        }
+        let name = &self.name;
+        let role = Role::Elements;
+        Sections {
+            linux: section::elf(role, name, hash),
+            macho: format!(
+                "__DATA,{},regular,no_dead_strip",
+                section::macho(role, hash),
+            ),
+            windows: format!("{}$b", section::windows(role, name, hash)),
+            illumos: section::illumos(role, name, hash),
+            bsd: section::elf(role, name, hash),
+        }
+    }
+}
+
+struct Sections {
+    linux: String,
+    macho: String,
+    windows: String,
+    illumos: String,
+    bsd: String,
+}
+
+const PRELUDE: &str = r"
+#ifndef LINKME_ELEMENT
+#define LINKME_ELEMENT(SLICE, name) LINKME_ELEMENT_##SLICE(name)
+
+#if defined(__cplusplus)
+#define LINKME_STATIC_ASSERT(expr, msg) static_assert(expr, msg)
+#define LINKME_ALIGNOF(type) alignof(type)
+#define LINKME_MSVC_EXTERN extern
+#else
+#define LINKME_STATIC_ASSERT(expr, msg) _Static_assert(expr, msg)
+#define LINKME_ALIGNOF(type) _Alignof(type)
+#define LINKME_MSVC_EXTERN
+#endif
+
+#if defined(__ELF__) && defined(__has_attribute)
+#if __has_attribute(retain)
+#define LINKME_USED __attribute__((used, retain))
+#endif
+#endif
+#ifndef LINKME_USED
+#define LINKME_USED __attribute__((used))
+#endif
+#endif
+";
@@ -0,0 +1,344 @@
+use crate::distributed_slice::DistributedSlice;
+#[cfg(any(feature = "alloc", feature = "fallback", target_family = "wasm"))]
+use alloc::boxed::Box;
+#[cfg(any(feature = "alloc", feature = "fallback", target_family = "wasm"))]
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::fmt::{self, Debug};
+use core::iter::FusedIterator;
+#[cfg(any(feature = "alloc", feature = "fallback", target_family = "wasm"))]
+use core::ptr;
+use core::slice;
+#[cfg(any(feature = "alloc", feature = "fallback", target_family = "wasm"))]
+use core::sync::atomic::AtomicPtr;
+#[cfg(any(feature = "alloc", feature = "fallback", target_family = "wasm"))]
+use core::sync::atomic::Ordering;
+#[cfg(not(any(feature = "alloc", feature = "fallback", target_family = "wasm")))]
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// Collection of static key-value pairs that are gathered into a contiguous
+/// section of the binary by the linker.
+///
+/// A distributed map is a [`DistributedSlice`] of entries, each consisting of
+/// a key and a reference to a static value, with lookup by key. Like the
+/// distributed slice, it involves no runtime initialization or allocation and
+/// is usable in `no_std` programs.
+///
+/// ## Declaration
+///
+/// A static distributed map may be declared by writing `#[distributed_map]` on
+/// a static item whose type is `Map<K, V>` for some key type `K` and value type
+/// `V`.
+///
+/// ```
+/// # #![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+/// #
+/// use linkme::distributed_map;
+///
+/// #[distributed_map]
+/// pub static ROUTES: Map<&str, fn() -> u16>;
+/// ```
+///
+/// The attribute rewrites the `Map<K, V>` type of the static into
+/// `DistributedMap<K, V>`.
+///
+/// ## Elements
+///
+/// Values may be registered into a distributed map by a
+/// `#[distributed_map(...)]` attribute in which the path to the distributed
+/// map and a const expression for the key are given in the parentheses. As
+/// with distributed slices, the attribute may be placed on a static or on a
+/// function definition, and elements may come from any crate linked into the
+/// final binary.
+///
+/// ```
+/// # #![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+/// #
+/// # use linkme::distributed_map;
+/// #
+/// # #[distributed_map]
+/// # pub static ROUTES: Map<&str, fn() -> u16>;
+/// #
+/// #[distributed_map(ROUTES, key = "/health")]
+/// fn health() -> u16 {
+///     200
+/// }
+///
+/// #[distributed_map(ROUTES, key = "/teapot")]
+/// static TEAPOT: fn() -> u16 = || 418;
+///
+/// fn main() {
+///     assert_eq!(ROUTES.get("/health").map(|route| route()), Some(200));
+///     assert!(ROUTES.contains_key("/teapot"));
+///     assert!(!ROUTES.contains_key("/missing"));
+/// }
+/// ```
+///
+/// ## Duplicate keys
+///
+/// The first access to a distributed map checks that no two of its elements
+/// have equal keys, and panics naming both elements and where they are
+/// defined if they do.
+///
+/// With linkme's `alloc` feature, the first access also sorts an index of the
+/// entries by key, through which lookups are a binary search. Without it, the
+/// check compares every pair of entries and lookups scan the entries linearly,
+/// which suits the small maps of a `no_std` program without an allocator.
+pub struct DistributedMap<K: 'static, V: 'static> {
+    entries: DistributedSlice<[MapEntry<K, V>]>,
+    index: &'static MapIndex,
+}
+
+#[doc(hidden)]
+pub struct MapEntry<K: 'static, V: 'static> {
+    key: K,
+    value: &'static V,
+    name: &'static str,
+    module_path: &'static str,
+    file: &'static str,
+    line: u32,
+}
+
+impl<K, V> MapEntry<K, V> {
+    #[doc(hidden)]
+    pub const fn private_new(
+        key: K,
+        value: &'static V,
+        name: &'static str,
+        module_path: &'static str,
+        file: &'static str,
+        line: u32,
+    ) -> Self {
+        MapEntry {
+            key,
+            value,
+            name,
+            module_path,
+            file,
+            line,
+        }
+    }
+}
+
+// State of a map computed at its first access. With an allocator, the
+// entries sorted by key, whose length is that of the map's slice. Otherwise
+// whether the keys have been checked for duplicates.
+#[doc(hidden)]
+pub struct MapIndex {
+    #[cfg(any(feature = "alloc", feature = "fallback", target_family = "wasm"))]
+    sorted: AtomicPtr<()>,
+    #[cfg(not(any(feature = "alloc", feature = "fallback", target_family = "wasm")))]
+    checked: AtomicBool,
+}
+
+impl MapIndex {
+    #[doc(hidden)]
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        MapIndex {
+            #[cfg(any(feature = "alloc", feature = "fallback", target_family = "wasm"))]
+            sorted: AtomicPtr::new(ptr::null_mut()),
+            #[cfg(not(any(feature = "alloc", feature = "fallback", target_family = "wasm")))]
+            checked: AtomicBool::new(false),
+        }
+    }
+}
+
+impl<K, V> DistributedMap<K, V> {
+    #[doc(hidden)]
+    pub const fn private_new(
+        entries: DistributedSlice<[MapEntry<K, V>]>,
+        index: &'static MapIndex,
+    ) -> Self {
+        DistributedMap { entries, index }
+    }
+
+    #[cfg(any(feature = "fallback", target_family = "wasm"))]
+    #[doc(hidden)]
+    pub fn private_register(&self, node: &'static crate::fallback::Node) {
+        self.entries.private_register(node);
+    }
+}
+
+impl<K: Ord, V> DistributedMap<K, V> {
+    #[cfg(any(feature = "alloc", feature = "fallback", target_family = "wasm"))]
+    fn sorted(self) -> &'static [&'static MapEntry<K, V>] {
+        let len = self.entries.static_slice().len();
+        let sorted = self.index.sorted.load(Ordering::Acquire);
+        if !sorted.is_null() {
+            return unsafe { slice::from_raw_parts(sorted.cast(), len) };
+        }
+
+        let mut entries: Vec<&'static MapEntry<K, V>> =
+            self.entries.static_slice().iter().collect();
+        entries.sort_unstable_by(|a, b| a.key.cmp(&b.key));
+        if let Some(pair) = entries.windows(2).find(|pair| pair[0].key == pair[1].key) {
+            self.duplicate(pair[0], pair[1]);
+        }
+
+        // Another thread may have raced to build the same index, in which case
+        // theirs is kept.
+        let entries = Box::into_raw(entries.into_boxed_slice());
+        match self.index.sorted.compare_exchange(
+            ptr::null_mut(),
+            entries.cast(),
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        ) {
+            Ok(_) => unsafe { &*entries },
+            Err(sorted) => {
+                drop(unsafe { Box::from_raw(entries) });
+                unsafe { slice::from_raw_parts(sorted.cast(), len) }
+            }
+        }
+    }
+
+    #[cfg(any(feature = "alloc", feature = "fallback", target_family = "wasm"))]
+    fn entries(self) -> &'static [MapEntry<K, V>] {
+        self.sorted();
+        self.entries.static_slice()
+    }
+
+    #[cfg(not(any(feature = "alloc", feature = "fallback", target_family = "wasm")))]
+    fn entries(self) -> &'static [MapEntry<K, V>] {
+        let entries = self.entries.static_slice();
+        if !self.index.checked.load(Ordering::Acquire) {
+            for (i, entry) in entries.iter().enumerate() {
+                if let Some(other) = entries[..i].iter().find(|other| other.key == entry.key) {
+                    self.duplicate(other, entry);
+                }
+            }
+            self.index.checked.store(true, Ordering::Release);
+        }
+        entries
+    }
+
+    fn duplicate(self, a: &MapEntry<K, V>, b: &MapEntry<K, V>) -> ! {
+        // Name the two elements in a consistent order regardless of how the
+        // linker laid them out.
+        let location =
+            |entry: &MapEntry<K, V>| (entry.module_path, entry.name, entry.file, entry.line);
+        let (first, second) = if location(a) <= location(b) {
+            (a, b)
+        } else {
+            (b, a)
+        };
+        panic!(
+            "duplicate key in #[distributed_map] with name \"{}\": {}::{} at {}:{} and {}::{} at {}:{}",
+            self.entries.name(),
+            first.module_path,
+            first.name,
+            first.file,
+            first.line,
+            second.module_path,
+            second.name,
+            second.file,
+            second.line,
+        );
+    }
+
+    /// Look up the value registered under the given key.
+    pub fn get<Q>(self, key: &Q) -> Option<&'static V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Ord,
+    {
+        #[cfg(any(feature = "alloc", feature = "fallback", target_family = "wasm"))]
+        {
+            let sorted = self.sorted();
+            let i = sorted
+                .binary_search_by(|entry| entry.key.borrow().cmp(key))
+                .ok()?;
+            Some(sorted[i].value)
+        }
+        #[cfg(not(any(feature = "alloc", feature = "fallback", target_family = "wasm")))]
+        {
+            self.entries()
+                .iter()
+                .find(|entry| entry.key.borrow() == key)
+                .map(|entry| entry.value)
+        }
+    }
+
+    /// Whether a value is registered under the given key.
+    pub fn contains_key<Q>(self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Ord,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Iterate the key-value pairs linked into this program, in unspecified
+    /// order.
+    pub fn iter(self) -> MapIter<K, V> {
+        MapIter {
+            entries: self.entries().iter(),
+        }
+    }
+
+    /// Number of key-value pairs linked into this program.
+    pub fn len(self) -> usize {
+        self.entries().len()
+    }
+
+    /// Whether no key-value pairs are linked into this program.
+    pub fn is_empty(self) -> bool {
+        self.entries().is_empty()
+    }
+}
+
+impl<K, V> Copy for DistributedMap<K, V> {}
+
+impl<K, V> Clone for DistributedMap<K, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K: Ord, V> IntoIterator for DistributedMap<K, V> {
+    type Item = (&'static K, &'static V);
+    type IntoIter = MapIter<K, V>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V> Debug for DistributedMap<K, V>
+where
+    K: Ord + Debug,
+    V: Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Iterator over the key-value pairs of a [`DistributedMap`].
+pub struct MapIter<K: 'static, V: 'static> {
+    entries: slice::Iter<'static, MapEntry<K, V>>,
+}
+
+impl<K, V> Iterator for MapIter<K, V> {
+    type Item = (&'static K, &'static V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next().map(|entry| (&entry.key, entry.value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for MapIter<K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.entries
+            .next_back()
+            .map(|entry| (&entry.key, entry.value))
+    }
+}
+
+impl<K, V> ExactSizeIterator for MapIter<K, V> {}
+
+impl<K, V> FusedIterator for MapIter<K, V> {}
@@ -1,19 +1,25 @@
+use crate::element_info::{ElementInfo, Entries};
+#[cfg(any(feature = "fallback", target_family = "wasm"))]
+use crate::fallback::{Node, Registry};
+use crate::force_link::{ForceLinked, Missing};
 use crate::private::Slice;
 use core::fmt::{self, Debug};
-#[cfg(any(target_os = "uefi", target_os = "windows"))]
 use core::hint;
 use core::mem;
 use core::num::NonZeroUsize;
 use core::ops::Deref;
+use core::ptr;
 use core::slice;
+use core::sync::atomic::{AtomicBool, Ordering};
 
 /// Collection of static elements that are gathered into a contiguous section of
 /// the binary by the linker.
 ///
 /// The implementation is based on `link_section` attributes and
 /// platform-specific linker support. It does not involve life-before-main or
-/// any other runtime initialization on any platform. This is a zero-cost safe
-/// abstraction that operates entirely during compilation and linking.
+/// any other runtime initialization on any platform with linker support. This
+/// is a zero-cost safe abstraction that operates entirely during compilation
+/// and linking.
 ///
 /// ## Declaration
 ///
@@ -130,6 +136,348 @@ use core::slice;
 ///     /* ... */
 /// }
 /// ```
+///
+/// A generic function is registered by listing the types to instantiate it
+/// with, as in `#[distributed_slice(BENCHMARKS, instantiate(u8, String))]`,
+/// which places one pointer per listed type into the slice. The function must
+/// have exactly one type parameter.
+///
+/// A `const fn` is registered the same way as any other function. With linkme's `alloc` feature
+/// enabled, an `async fn` may be registered too, as a pointer to an adapter of
+/// type `fn(...) -> Pin<Box<dyn Future<Output = R> + Send>>` which boxes the
+/// future returned by the function. If the function's arguments borrow, with
+/// `&` or `'_`, the future borrows for as long as they do, so the adapter is a
+/// `for<'a> fn(&'a Request) -> Pin<Box<dyn Future<Output = R> + Send + 'a>>`
+/// with every such lifetime named `'a`.
+///
+/// ## Associated functions
+///
+/// An attribute macro on a function inside an impl block cannot add items next
+/// to the impl block, so functions inside of one are registered by writing
+/// `#[linkme::register]` on the whole impl block. Each of its functions that
+/// carries a `#[distributed_slice(...)]` attribute is placed into the slice as
+/// a pointer to `Self::function`.
+///
+/// ```
+/// # #![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+/// #
+/// # pub struct Event;
+/// #
+/// use linkme::distributed_slice;
+///
+/// pub struct Logger;
+///
+/// #[distributed_slice]
+/// pub static HANDLERS: [fn(&Logger, &Event)];
+///
+/// #[linkme::register]
+/// impl Logger {
+///     #[distributed_slice(HANDLERS)]
+///     fn on_event(&self, event: &Event) {
+///         /* ... */
+///     }
+/// }
+/// ```
+///
+/// ## Trait implementations
+///
+/// For a distributed slice of trait objects, the attribute may be applied to
+/// an impl block or to a unit struct to register a reference to that struct,
+/// coerced to the element type of the slice.
+///
+/// ```
+/// # #![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+/// #
+/// use linkme::distributed_slice;
+///
+/// pub trait Plugin: Sync {
+///     fn name(&self) -> &'static str;
+/// }
+///
+/// #[distributed_slice]
+/// pub static PLUGINS: [&'static dyn Plugin];
+///
+/// pub struct JsonPlugin;
+///
+/// // Equivalent to:
+/// //
+/// //    #[distributed_slice(PLUGINS)]
+/// //    static _: &'static dyn Plugin = &JsonPlugin;
+/// //
+/// #[distributed_slice(PLUGINS)]
+/// impl Plugin for JsonPlugin {
+///     fn name(&self) -> &'static str {
+///         "json"
+///     }
+/// }
+/// ```
+///
+/// ## Multiple slices
+///
+/// Listing several slices, as in `#[distributed_slice(COMMANDS, HELP_TOPICS)]`,
+/// registers one element into each of them. The item is type checked against
+/// the element type of every listed slice. A position given after the last
+/// slice applies to each of the elements.
+///
+/// ## Anonymous elements
+///
+/// Elements that need no name of their own, such as ones produced by a
+/// `macro_rules` macro, may be registered by the function-like
+/// `distributed_slice_element!` macro. Any number of these may appear in the
+/// same scope.
+///
+/// ```
+/// # #![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+/// #
+/// # pub struct Bencher;
+/// #
+/// use linkme::{distributed_slice, distributed_slice_element};
+///
+/// #[distributed_slice]
+/// pub static BENCHMARKS: [fn(&mut Bencher)];
+///
+/// distributed_slice_element!(BENCHMARKS => |b| { /* ... */ });
+/// distributed_slice_element!(BENCHMARKS => |b| { /* ... */ });
+/// ```
+///
+/// The element type is that of the slice, and a `#[linkme(crate = ...)]`
+/// attribute may precede the path to the slice.
+///
+/// ## Zero-sized elements
+///
+/// The element type may be zero-sized, for example a unit struct registered
+/// once per enabled module where only the number of registrations matters.
+/// Each such element is accompanied by a small marker in the binary so that
+/// `len()`, iteration and indexing behave as for any other `&'static [T]`.
+///
+/// The markers rely on the linker placing them back to back, which PE/COFF
+/// linkers do not promise, so a zero-sized element fails to compile on
+/// Windows and UEFI.
+///
+/// ## Element order
+///
+/// The order of elements in a distributed slice is unspecified by default. On
+/// Windows and UEFI, an element may be given an explicit position from 0 to
+/// 9999 by writing `#[distributed_slice(BENCHMARKS, 5)]`, and the linker will
+/// sort positioned elements in ascending order after all unpositioned ones.
+/// The [fallback backend](#fallback-backend), including on WebAssembly, sorts
+/// them the same way.
+///
+/// Positions are not supported on ELF, Mach-O, illumos, or BSD targets, whose
+/// linkers have no way to sort the sections that make up a distributed slice.
+/// A positioned element is a compile error there rather than an element
+/// silently missing from the slice.
+///
+/// ## Cardinality
+///
+/// A declaration may say how many elements it expects, using `min = N`,
+/// `max = N`, or `exact = N`. The count is checked the first time the slice is
+/// accessed, where a violation panics with a message naming the slice, its
+/// element count, and the expected bound.
+///
+/// ```
+/// # #![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+/// #
+/// # pub trait Backend {}
+/// #
+/// use linkme::distributed_slice;
+///
+/// #[distributed_slice(min = 1)]
+/// pub static BACKENDS: [fn() -> Box<dyn Backend>];
+/// #
+/// # struct Null;
+/// # impl Backend for Null {}
+/// #
+/// # #[distributed_slice(BACKENDS)]
+/// # static NULL: fn() -> Box<dyn Backend> = || Box::new(Null);
+/// #
+/// # fn main() {
+/// #     assert_eq!(BACKENDS.len(), 1);
+/// # }
+/// ```
+///
+/// On ELF targets a minimum of one or more is also enforced by the linker:
+/// every element provides a weak symbol that the slice refers to, so a binary
+/// in which no element was linked fails with an undefined reference to
+/// `linkme_empty_distributed_slice_BACKENDS_*`. Elements defined through the
+/// [C header](crate::build) provide the symbol too.
+///
+/// A maximum of one is enforced by the linker on every platform: each Rust
+/// element of such a slice is exported under one symbol name, so that a second
+/// element fails as a multiple definition of
+/// `linkme_too_many_elements_distributed_slice_BACKENDS_*`. Any other bound is
+/// left to the runtime check.
+///
+/// ## Crates that only contribute elements
+///
+/// A crate that is listed as a dependency but never referenced is not linked
+/// into the program at all, and neither are any elements it defines. The
+/// program can name such crates, as in
+/// `linkme::force_link!(json_plugin, yaml_plugin);`, to link them anyway.
+///
+/// A crate may further be named together with a slice it is expected to
+/// contribute to, as in `linkme::force_link!(json_plugin => PLUGINS);`, if the
+/// slice is declared with `#[linkme(force_link)]`. The first access to such a
+/// slice panics listing the crates expected to contribute to it that
+/// contributed no element. A crate is matched against the first segment of
+/// each element's `module_path!()`, so it must be named in `force_link!` by
+/// its own crate name rather than a name given to it in Cargo.toml. This
+/// requires recording [element info][Self::entries] and so implies
+/// `#[linkme(element_info)]`.
+///
+/// ```
+/// # #![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+/// #
+/// # pub trait Plugin {}
+/// #
+/// use linkme::distributed_slice;
+///
+/// #[distributed_slice]
+/// #[linkme(force_link)]
+/// pub static PLUGINS: [fn() -> Box<dyn Plugin>];
+/// ```
+///
+/// ## Section name
+///
+/// The linker section holding the elements is named after the slice and a
+/// hash of its name, unless the declaration gives a name of its own, for
+/// example to be found by a linker script or by tooling that inspects the
+/// binary.
+///
+/// ```
+/// # #![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+/// #
+/// # struct Bencher;
+/// #
+/// # use linkme::distributed_slice;
+/// #
+/// #[distributed_slice]
+/// #[linkme(section = "benchmarks")]
+/// pub static BENCHMARKS: [fn(&mut Bencher)];
+/// ```
+///
+/// Elements pick up the name from the declaration. The name must be unique
+/// within the binary and is checked against the object format of the target:
+///
+/// - On ELF targets it must be a C identifier, since the linker only defines
+///   `__start_` and `__stop_` symbols for such sections. On illumos it must
+///   additionally begin with `set_`.
+/// - On Mach-O targets it must be at most 16 bytes, and it is placed in the
+///   `__DATA` segment.
+/// - On PE/COFF targets it must not contain `$`, since the elements are
+///   placed in `NAME$b` between `NAME$a` and `NAME$c`.
+///
+/// ## Extern sections
+///
+/// A distributed slice may also give typed access to a section that linkme
+/// did not create, such as a C array placed by
+/// `__attribute__((section("my_table")))` or a table emitted by assembly.
+///
+/// ```
+/// # #![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+/// #
+/// # #[repr(C)]
+/// # pub struct Entry(u32);
+/// #
+/// # #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+/// # core::arch::global_asm!(".pushsection my_table,\"awR\"", ".popsection");
+/// #
+/// use linkme::distributed_slice;
+///
+/// # #[cfg(not(linkme_fallback))]
+/// #[distributed_slice(unsafe(extern_section = "my_table"))]
+/// pub static TABLE: [Entry];
+/// #
+/// # fn main() {}
+/// ```
+///
+/// Declaring the slice is unsafe because linkme cannot check what the section
+/// contains: every byte of it must make up a valid `T` at the stride of
+/// `size_of::<T>()`, and padding inserted between the contributions of
+/// different object files must not break that. Once declared, the slice is
+/// used like any other.
+///
+/// No section is created by the declaration itself, so the section must exist
+/// and survive the linker's garbage collection, for example by marking the C
+/// array `__attribute__((used, retain))`. The name is checked as described
+/// under *Section name*. On PE/COFF targets the foreign entries must be placed
+/// in a section `NAME$b`, between the boundaries that linkme emits in `NAME$a`
+/// and `NAME$c`. Since extern sections are not subject to the duplicate check,
+/// several declarations may view the same section, and elements registered
+/// with `#[distributed_slice(TABLE)]` are added to it.
+///
+/// ## Elements from C
+///
+/// C and C++ code compiled by a build script may contribute elements through a
+/// header generated by [`linkme::build::CHeader`][crate::build::CHeader],
+/// available with linkme's `build` feature enabled in the build-dependency.
+///
+/// ## Dynamically loaded objects
+///
+/// A distributed slice is scoped to one linked image: the executable, or a
+/// shared object such as a `dylib` or `cdylib`. The linker resolves the
+/// slice's bounds separately within each image, so the slice holds exactly the
+/// elements linked into the image containing its declaration.
+///
+/// - A slice declared in a shared object does not include elements added by
+///   the executable or by other shared objects, even ones that link against it.
+///
+/// - Elements added by a plugin loaded with `dlopen` are not included in any
+///   slice of the host.
+///
+/// - A crate linked into more than one image, statically into each, gives each
+///   image its own copy of the slice, holding that image's elements.
+///
+/// On Linux, linkme's `dynamic` feature provides [`DistributedSlice::image`]
+/// telling which image a slice belongs to,
+/// [`linkme::dynamic::check`][crate::dynamic::check] reporting elements that
+/// were linked into a different image from the slice, and
+/// [`linkme::dynamic::elements`][crate::dynamic::elements] finding the slice's
+/// elements in every loaded image.
+///
+/// ## Fallback backend
+///
+/// WebAssembly has no linker sections in linear memory, so distributed slices
+/// on `target_family = "wasm"` always use a fallback that registers elements
+/// at runtime. On any other target not supported by the linker-based
+/// implementation, such as NetBSD, declaring a distributed slice is a compile
+/// error unless linkme's `fallback` feature is enabled. The fallback keeps each
+/// slice's elements in a lock-free list, onto which every element is pushed by
+/// a constructor that runs before `main`. The first access to the slice copies
+/// the elements into a single allocation, so the slice still behaves like
+/// `&'static [T]`, and elements registered after that access are not seen.
+///
+/// - The elements of the slice are copies of the element statics, so a static
+///   with interior mutability is not the same object as its element.
+///
+/// - On WebAssembly, the constructors are run by `__wasm_call_ctors`, which a
+///   WASI command calls before `main` and wasm-bindgen calls on load. Any
+///   other module, such as a library instantiated directly by its host, must
+///   call [`linkme::init`][crate::init] before the first access to any slice.
+///
+/// - Section names and extern sections have no meaning for the fallback, and
+///   extern sections are rejected.
+///
+/// Building with `--cfg linkme_fallback` in `RUSTFLAGS` selects the fallback
+/// on every target, which is how it is tested.
+///
+/// ## Linker scripts
+///
+/// A program linked with its own linker script, as is usual on
+/// `target_os = "none"`, must keep each slice's sections, for example through
+/// a fragment generated by
+/// [`linkme::build::LinkerScript`][crate::build::LinkerScript]. If the linker
+/// discards them, the first access to the slice panics instead of finding it
+/// empty.
+///
+/// ## Duplicate declarations
+///
+/// If two declarations of the same distributed slice end up linked into one
+/// binary, for example through two differently configured builds of one crate,
+/// the first access to the slice panics. Enabling linkme's `link_dupcheck`
+/// feature instead makes each declaration define a strong symbol named after
+/// the slice, so that the duplicate is reported by the linker as a multiple
+/// definition of `linkme_duplicate_distributed_slice_{NAME}_{HASH}`.
 pub struct DistributedSlice<T: ?Sized + Slice> {
     name: &'static str,
     stride: NonZeroUsize,
@@ -137,10 +485,25 @@ pub struct DistributedSlice<T: ?Sized + Slice> {
     section_stop: StaticPtr<T::Element>,
     dupcheck_start: StaticPtr<isize>,
     dupcheck_stop: StaticPtr<isize>,
+    info_start: StaticPtr<ElementInfo>,
+    info_stop: StaticPtr<ElementInfo>,
+    min: usize,
+    max: usize,
+    nonempty: StaticPtr<u8>,
+    force_link: Option<(
+        &'static AtomicBool,
+        &'static DistributedSlice<[ForceLinked]>,
+    )>,
+    #[cfg_attr(not(all(feature = "dynamic", target_os = "linux")), allow(dead_code))]
+    elf_section: &'static str,
+    #[cfg_attr(not(all(feature = "dynamic", target_os = "linux")), allow(dead_code))]
+    elf_bounds: &'static str,
+    #[cfg(any(feature = "fallback", target_family = "wasm"))]
+    registry: Option<&'static Registry>,
 }
 
-struct StaticPtr<T> {
-    ptr: *const T,
+pub(crate) struct StaticPtr<T> {
+    pub(crate) ptr: *const T,
 }
 
 unsafe impl<T> Send for StaticPtr<T> {}
@@ -165,8 +528,16 @@ impl<T> DistributedSlice<[T]> {
         dupcheck_start: *const isize,
         dupcheck_stop: *const isize,
     ) -> Self {
-        let Some(stride) = NonZeroUsize::new(mem::size_of::<T>()) else {
-            panic!("#[distributed_slice] requires that the slice element type has nonzero size");
+        // Zero-sized elements take up no room in the section, so each one is
+        // accompanied by a marker of `align_of::<T>()` bytes that is counted
+        // instead. See private::zst_marker_len.
+        let size = if mem::size_of::<T>() == 0 {
+            mem::align_of::<T>()
+        } else {
+            mem::size_of::<T>()
+        };
+        let Some(stride) = NonZeroUsize::new(size) else {
+            unreachable!();
         };
 
         DistributedSlice {
@@ -178,9 +549,183 @@ impl<T> DistributedSlice<[T]> {
                 ptr: dupcheck_start,
             },
             dupcheck_stop: StaticPtr { ptr: dupcheck_stop },
+            info_start: StaticPtr { ptr: ptr::null() },
+            info_stop: StaticPtr { ptr: ptr::null() },
+            min: 0,
+            max: usize::MAX,
+            nonempty: StaticPtr { ptr: ptr::null() },
+            force_link: None,
+            elf_section: "",
+            elf_bounds: "",
+            #[cfg(any(feature = "fallback", target_family = "wasm"))]
+            registry: None,
+        }
+    }
+
+    // A slice whose elements are registered at runtime by the fallback
+    // backend. A non-null info_start records that the declaration opted in to
+    // element_info; the infos themselves come from the registry.
+    #[cfg(any(feature = "fallback", target_family = "wasm"))]
+    #[doc(hidden)]
+    pub const fn private_registry(
+        name: &'static str,
+        registry: &'static Registry,
+        element_info: bool,
+    ) -> Self {
+        let info_start = if element_info {
+            ptr::NonNull::dangling().as_ptr()
+        } else {
+            ptr::null()
+        };
+        let Some(stride) = NonZeroUsize::new(1) else {
+            unreachable!();
+        };
+
+        DistributedSlice {
+            name,
+            stride,
+            section_start: StaticPtr { ptr: ptr::null() },
+            section_stop: StaticPtr { ptr: ptr::null() },
+            dupcheck_start: StaticPtr { ptr: ptr::null() },
+            dupcheck_stop: StaticPtr { ptr: ptr::null() },
+            info_start: StaticPtr { ptr: info_start },
+            info_stop: StaticPtr { ptr: info_start },
+            min: 0,
+            max: usize::MAX,
+            nonempty: StaticPtr { ptr: ptr::null() },
+            force_link: None,
+            elf_section: "",
+            elf_bounds: "",
+            registry: Some(registry),
+        }
+    }
+
+    #[cfg(any(feature = "fallback", target_family = "wasm"))]
+    #[doc(hidden)]
+    pub fn private_register(&self, node: &'static Node) {
+        if let Some(registry) = self.registry {
+            registry.register(node);
+        }
+    }
+
+    // Name of the section on ELF targets, by which linkme::dynamic finds the
+    // slice's elements in other loaded objects, and of the symbol under which
+    // the declaration exports the section's bounds.
+    #[doc(hidden)]
+    #[must_use]
+    pub const fn private_elf_section(
+        self,
+        elf_section: &'static str,
+        elf_bounds: &'static str,
+    ) -> Self {
+        DistributedSlice {
+            elf_section,
+            elf_bounds,
+            ..self
+        }
+    }
+
+    #[doc(hidden)]
+    #[must_use]
+    pub const unsafe fn private_element_info(
+        self,
+        info_start: *const ElementInfo,
+        info_stop: *const ElementInfo,
+    ) -> Self {
+        DistributedSlice {
+            info_start: StaticPtr { ptr: info_start },
+            info_stop: StaticPtr { ptr: info_stop },
+            ..self
+        }
+    }
+
+    // Number of elements allowed by #[distributed_slice(min = .., max = ..)].
+    #[doc(hidden)]
+    #[must_use]
+    pub const fn private_cardinality(self, min: usize, max: usize) -> Self {
+        DistributedSlice { min, max, ..self }
+    }
+
+    // A symbol defined by every element of a slice that requires one, so that
+    // a program linking no element fails to link instead of panicking.
+    #[doc(hidden)]
+    #[must_use]
+    pub const unsafe fn private_nonempty(self, nonempty: *const u8) -> Self {
+        DistributedSlice {
+            nonempty: StaticPtr { ptr: nonempty },
+            ..self
         }
     }
 
+    // Set by #[linkme(force_link)], whose check runs at the first access. The
+    // crates expected to contribute are the elements of a second slice, which
+    // force_link!(krate => SLICE) adds to.
+    #[doc(hidden)]
+    #[must_use]
+    pub const fn private_force_link(
+        self,
+        checked: &'static AtomicBool,
+        expectations: &'static DistributedSlice<[ForceLinked]>,
+    ) -> Self {
+        DistributedSlice {
+            force_link: Some((checked, expectations)),
+            ..self
+        }
+    }
+
+    // The slice to which force_link! registers its expectations under the
+    // fallback backend.
+    #[cfg(any(feature = "fallback", target_family = "wasm"))]
+    #[doc(hidden)]
+    pub fn private_force_linked(self) -> DistributedSlice<[ForceLinked]> {
+        match self.force_link {
+            Some((_checked, expectations)) => *expectations,
+            None => unreachable!(),
+        }
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        self.name
+    }
+
+    #[cfg(all(feature = "dynamic", target_os = "linux"))]
+    pub(crate) fn elf_section(self) -> &'static str {
+        self.elf_section
+    }
+
+    #[cfg(all(feature = "dynamic", target_os = "linux"))]
+    pub(crate) fn elf_bounds(self) -> &'static str {
+        self.elf_bounds
+    }
+
+    /// The executable or shared object whose elements this slice holds.
+    ///
+    /// Only available on Linux with linkme's `dynamic` feature.
+    /// See [Dynamically loaded objects](#dynamically-loaded-objects).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slice's section lies outside every object reported by
+    /// the dynamic loader, which is not expected to happen.
+    ///
+    /// ```no_run
+    /// # #![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+    /// #
+    /// use linkme::distributed_slice;
+    ///
+    /// #[distributed_slice]
+    /// pub static COMMANDS: [fn()];
+    ///
+    /// fn main() {
+    ///     println!("commands from {}", COMMANDS.image().path().display());
+    /// }
+    /// ```
+    #[cfg(all(feature = "dynamic", target_os = "linux"))]
+    pub fn image(self) -> crate::dynamic::Image {
+        crate::dynamic::image_containing(self.section_start.ptr as usize)
+            .expect("distributed slice is not in any loaded object")
+    }
+
     #[doc(hidden)]
     #[inline]
     pub unsafe fn private_typecheck(self, get: fn() -> &'static T) {
@@ -223,6 +768,14 @@ impl<T> DistributedSlice<[T]> {
     /// }
     /// ```
     pub fn static_slice(self) -> &'static [T] {
+        #[cfg(any(feature = "fallback", target_family = "wasm"))]
+        if let Some(registry) = self.registry {
+            let slice = unsafe { registry.materialize().0 };
+            self.check_cardinality(slice.len());
+            self.check_force_link();
+            return slice;
+        }
+
         // On Windows/UEFI, boundary elements are non-ZST (MaybeUninit<T> and
         // isize) so dupcheck and slice boundary arithmetic must account for
         // their size.
@@ -231,22 +784,148 @@ impl<T> DistributedSlice<[T]> {
             panic!("duplicate #[distributed_slice] with name \"{}\"", self.name);
         }
 
-        let start = unsafe { self.section_start.ptr.add(skip) };
-        let stop = self.section_stop.ptr;
-        let byte_offset = stop as usize - start as usize;
-        let len = byte_offset / self.stride;
+        // The declaration's own dupcheck marker is missing only if the linker
+        // discarded the slice's sections, which would leave the slice empty.
+        if !self.dupcheck_start.ptr.is_null()
+            && self.dupcheck_start.ptr.wrapping_add(skip) == self.dupcheck_stop.ptr
+        {
+            panic!(
+                "sections of #[distributed_slice] with name \"{}\" were discarded by the linker; the linker script must KEEP them",
+                self.name,
+            );
+        }
+
+        let slice = unsafe { section(self.section_start, self.section_stop, self.stride) };
+        self.check_cardinality(slice.len());
+        self.check_force_link();
+        slice
+    }
+
+    fn check_cardinality(self, len: usize) {
+        // Keeps the reference to the symbol from being optimized out.
+        hint::black_box(self.nonempty.ptr);
+
+        if len < self.min || len > self.max {
+            let plural = if len == 1 { "" } else { "s" };
+            let expected = if self.min == self.max {
+                "exactly"
+            } else if len < self.min {
+                "at least"
+            } else {
+                "at most"
+            };
+            let bound = if len < self.min { self.min } else { self.max };
+            panic!(
+                "#[distributed_slice] with name \"{}\" has {} element{}, expected {} {}",
+                self.name, len, plural, expected, bound,
+            );
+        }
+    }
+
+    fn check_force_link(self)
+    where
+        T: 'static,
+    {
+        let Some((checked, expectations)) = self.force_link else {
+            return;
+        };
+        if !checked.load(Ordering::Acquire) {
+            let missing = Missing::new(expectations.static_slice(), self.infos());
+            if !missing.is_empty() {
+                panic!(
+                    "#[distributed_slice] with name \"{}\" has no elements from force-linked crates: {}",
+                    self.name, missing,
+                );
+            }
+            checked.store(true, Ordering::Release);
+        }
+    }
+
+    /// Iterate the elements linked into this program together with the source
+    /// information recorded for each one.
+    ///
+    /// Returns `None` unless the slice's declaration opts in by
+    /// `#[linkme(element_info)]`, since no information is recorded for the
+    /// elements otherwise. The entries are produced in an unspecified order
+    /// which need not match the order of `static_slice()`.
+    ///
+    /// ```
+    /// # #![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+    /// #
+    /// use linkme::distributed_slice;
+    ///
+    /// #[distributed_slice]
+    /// #[linkme(element_info)]
+    /// pub static HANDLERS: [fn()];
+    ///
+    /// #[distributed_slice(HANDLERS)]
+    /// fn on_request() {
+    ///     /* ... */
+    /// }
+    ///
+    /// fn main() {
+    ///     for (handler, info) in HANDLERS.entries().unwrap() {
+    ///         println!("handler {} from {}:{}", info.name(), info.file(), info.line());
+    /// #       let _ = handler;
+    ///     }
+    /// }
+    /// ```
+    pub fn entries(self) -> Option<Entries<T>> {
+        if self.info_start.ptr.is_null() {
+            return None;
+        }
+
+        // Touch the slice to run the dupcheck.
+        let _ = self.static_slice();
 
-        // On Windows, the implementation involves growing a &[T; 0] to
-        // encompass elements that we have asked the linker to place immediately
-        // after that location. The compiler sees this as going "out of bounds"
-        // based on provenance, so we must conceal what is going on.
-        #[cfg(any(target_os = "uefi", target_os = "windows"))]
-        let start = hint::black_box(start);
+        Some(Entries::new(self.infos()))
+    }
 
-        unsafe { slice::from_raw_parts(start, len) }
+    fn infos(self) -> &'static [ElementInfo]
+    where
+        T: 'static,
+    {
+        #[cfg(any(feature = "fallback", target_family = "wasm"))]
+        if let Some(registry) = self.registry {
+            return unsafe { registry.materialize::<T>().1 };
+        }
+
+        let stride = NonZeroUsize::new(mem::size_of::<ElementInfo>()).unwrap();
+        unsafe { section(self.info_start, self.info_stop, stride) }
     }
 }
 
+unsafe fn section<T>(
+    section_start: StaticPtr<T>,
+    section_stop: StaticPtr<T>,
+    stride: NonZeroUsize,
+) -> &'static [T] {
+    // On Windows/UEFI, the start boundary element is non-ZST so the first
+    // element is located one stride past it.
+    let skip = usize::from(cfg!(any(target_os = "uefi", target_os = "windows")));
+    let start = unsafe { section_start.ptr.add(skip) };
+    let stop = section_stop.ptr;
+    let byte_offset = stop as usize - start as usize;
+    let len = byte_offset / stride;
+
+    // On Windows, the implementation involves growing a &[T; 0] to
+    // encompass elements that we have asked the linker to place immediately
+    // after that location. The compiler sees this as going "out of bounds"
+    // based on provenance, so we must conceal what is going on.
+    #[cfg(any(target_os = "uefi", target_os = "windows"))]
+    let start = hint::black_box(start);
+
+    // Zero-sized elements need not be located at the section's address, which
+    // might not be aligned for them.
+    let start = if mem::size_of::<T>() == 0 {
+        ptr::NonNull::dangling().as_ptr()
+    } else {
+        start
+    };
+
+    unsafe { slice::from_raw_parts(start, len) }
+}
+
 impl<T> Copy for DistributedSlice<[T]> {}
 
 impl<T> Clone for DistributedSlice<[T]> {
@@ -0,0 +1,191 @@
+use crate::distributed_slice::{DistributedSlice, StaticPtr};
+use core::fmt::{self, Debug};
+use core::ops::Deref;
+
+/// Static with exactly one definition, supplied by any crate in the dependency
+/// graph of the final binary.
+///
+/// A distributed slot is the single-element counterpart of a
+/// [`DistributedSlice`][crate::DistributedSlice], for hooks of the form "the
+/// application provides exactly one X". Like the distributed slice, it
+/// involves no runtime initialization.
+///
+/// ## Declaration
+///
+/// A static distributed slot may be declared by writing `#[distributed_slot]`
+/// on a static item without an initializer.
+///
+/// ```
+/// # #![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+/// #
+/// use linkme::distributed_slot;
+///
+/// #[distributed_slot]
+/// pub static CLOCK: fn() -> u64;
+/// #
+/// # #[distributed_slot(CLOCK)]
+/// # static SYSTEM_CLOCK: fn() -> u64 = || 0;
+/// ```
+///
+/// The attribute rewrites the `T` type of the static into
+/// `DistributedSlot<T>`, which dereferences to `&'static T`.
+///
+/// ## Definition
+///
+/// The slot is defined by a `#[distributed_slot(...)]` attribute in which the
+/// path to the distributed slot is given in the parentheses, on either a
+/// static or a function.
+///
+/// ```
+/// # #![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+/// #
+/// # use linkme::distributed_slot;
+/// #
+/// # #[distributed_slot]
+/// # pub static CLOCK: fn() -> u64;
+/// #
+/// #[distributed_slot(CLOCK)]
+/// fn monotonic_clock() -> u64 {
+///     /* ... */
+/// #   0
+/// }
+///
+/// fn main() {
+///     let now = CLOCK();
+/// #   let _ = now;
+/// }
+/// ```
+///
+/// A program that uses a distributed slot but links no definition of it fails
+/// to link with an undefined symbol, and a program that links more than one
+/// definition fails to link with a duplicate symbol. Either way the symbol is
+/// named `linkme_distributed_slot_{NAME}_{HASH}`.
+///
+/// ## Default value
+///
+/// The declaring crate may instead give the slot a default value. Such a slot
+/// always has a value, and any one crate in the dependency graph may replace
+/// it by writing `#[distributed_slot(NAME, override)]`.
+///
+/// ```
+/// # #![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+/// #
+/// use linkme::distributed_slot;
+///
+/// #[distributed_slot]
+/// pub static GREETING: &str = "hello";
+///
+/// #[distributed_slot(GREETING, override)]
+/// static CUSTOM_GREETING: &str = "howdy";
+///
+/// fn main() {
+///     assert_eq!(*GREETING, "howdy");
+///     assert!(GREETING.is_overridden());
+/// }
+/// ```
+///
+/// Linking more than one override of the same slot fails with a duplicate
+/// `linkme_distributed_slot_override_{NAME}_{HASH}` symbol. Definitions
+/// without `override` are rejected for a slot that has a default value, and
+/// `override` is rejected for a slot that has none.
+pub struct DistributedSlot<T: 'static> {
+    name: &'static str,
+    value: StaticPtr<T>,
+    overrides: Option<DistributedSlice<[T]>>,
+}
+
+impl<T> DistributedSlot<T> {
+    #[doc(hidden)]
+    pub const unsafe fn private_new(name: &'static str, value: *const T) -> Self {
+        DistributedSlot {
+            name,
+            value: StaticPtr { ptr: value },
+            overrides: None,
+        }
+    }
+
+    #[doc(hidden)]
+    pub const unsafe fn private_with_default(
+        name: &'static str,
+        default: *const T,
+        overrides: DistributedSlice<[T]>,
+    ) -> Self {
+        DistributedSlot {
+            name,
+            value: StaticPtr { ptr: default },
+            overrides: Some(overrides),
+        }
+    }
+
+    #[cfg(any(feature = "fallback", target_family = "wasm"))]
+    #[doc(hidden)]
+    pub fn private_register(&self, node: &'static crate::fallback::Node) {
+        if let Some(overrides) = &self.overrides {
+            overrides.private_register(node);
+        }
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub unsafe fn private_typecheck(self, get: fn() -> &'static T) {
+        let _ = get;
+    }
+
+    /// Retrieve the one definition of this slot linked into this program.
+    ///
+    /// For a slot with a default value, this is the override if one is linked
+    /// and the default otherwise.
+    pub fn get(self) -> &'static T {
+        match self.overrides {
+            Some(overrides) => match overrides.static_slice() {
+                [] => unsafe { &*self.value.ptr },
+                [value] => value,
+                [..] => panic!(
+                    "#[distributed_slot] with name \"{}\" has more than one override",
+                    self.name,
+                ),
+            },
+            None => unsafe { &*self.value.ptr },
+        }
+    }
+
+    /// Whether the value of this slot comes from an override rather than the
+    /// default supplied by its declaration.
+    ///
+    /// Always false for a slot declared without a default value.
+    pub fn is_overridden(self) -> bool {
+        match self.overrides {
+            Some(overrides) => !overrides.static_slice().is_empty(),
+            None => false,
+        }
+    }
+
+    /// Name of the slot as declared.
+    pub fn name(self) -> &'static str {
+        self.name
+    }
+}
+
+impl<T> Copy for DistributedSlot<T> {}
+
+impl<T> Clone for DistributedSlot<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Deref for DistributedSlot<T> {
+    type Target = T;
+    fn deref(&self) -> &'static Self::Target {
+        self.get()
+    }
+}
+
+impl<T> Debug for DistributedSlot<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        Debug::fmt(self.get(), formatter)
+    }
+}
@@ -0,0 +1,510 @@
+//! Elements of a distributed slice across dynamically loaded objects.
+//!
+//! A distributed slice covers the elements linked into one image, the
+//! executable or a shared object, since the linker resolves the slice's
+//! section bounds separately for each. A plugin built as a `cdylib` and loaded
+//! with `dlopen` therefore has its own copy of the section, which the host's
+//! slice never sees.
+//!
+//! This module finds the slice's section in every object currently loaded into
+//! the process, by walking the objects with `dl_iterate_phdr` and looking up
+//! the `__start_` and `__stop_` symbols that the linker defines for the
+//! section in each one's dynamic symbol table with `dlsym`. Where a version
+//! script keeps those out of the table, as rustc's does for a `cdylib` linked
+//! by GNU ld or gold, it looks up the section's bounds exported by the slice's
+//! declaration instead. It is available on Linux with linkme's `dynamic`
+//! feature.
+//!
+//! ```no_run
+//! # #![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+//! #
+//! use linkme::distributed_slice;
+//!
+//! #[distributed_slice]
+//! pub static COMMANDS: [fn()];
+//!
+//! fn main() {
+//!     // ... dlopen plugins ...
+//!
+//!     for (image, commands) in unsafe { linkme::dynamic::images(COMMANDS) } {
+//!         println!("{} commands in {}", commands.len(), image.path().display());
+//!     }
+//! }
+//! ```
+//!
+//! To find out whether elements were linked into objects other than the one
+//! holding the slice, and so are missing from it, use [`check`].
+//!
+//! Every object must agree on the element type, which holds when they were
+//! built against the same copy of the crate declaring the slice, since the
+//! section is named after the declaring crate, its version, its package and
+//! the slice.
+//!
+//! # Limitations
+//!
+//! - Shared objects export the section's bounds, but an executable does so
+//!   only when linked with `-rdynamic`. Elements in the executable are
+//!   therefore found only if the executable holds the slice, or was linked
+//!   that way.
+//!
+//! - The dynamic loader offers no notification when an object is loaded, so
+//!   nothing is reported until a [`Watcher`] is polled, for example right
+//!   after loading plugins.
+
+use crate::private::SectionBounds;
+use crate::DistributedSlice;
+use core::ffi::{c_char, c_int, c_void, CStr};
+use core::fmt::{self, Display};
+use core::mem;
+use core::ops::RangeInclusive;
+use core::ptr::{self, NonNull};
+use core::slice;
+use std::collections::HashSet;
+use std::error::Error;
+use std::ffi::{CString, OsStr};
+use std::os::unix::ffi::OsStrExt;
+use std::path::{Path, PathBuf};
+use std::vec::{self, Vec};
+
+/// An executable or shared object loaded into the process.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Image {
+    path: PathBuf,
+    base: usize,
+    executable: bool,
+    // Addresses covered by the object's loadable segments. An empty section
+    // may sit right at the end of a segment.
+    segments: Vec<RangeInclusive<usize>>,
+}
+
+impl Image {
+    /// Path of the object's file, as given to the dynamic loader. The
+    /// executable is reported as `/proc/self/exe`.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Difference between the addresses in the object's file and where it is
+    /// loaded in memory.
+    pub fn base(&self) -> usize {
+        self.base
+    }
+
+    fn contains(&self, addr: usize) -> bool {
+        self.segments.iter().any(|segment| segment.contains(&addr))
+    }
+}
+
+/// Iterator over loaded objects together with their elements of a slice.
+///
+/// Returned by [`images`] and [`Watcher::poll`].
+pub struct Images<T: 'static> {
+    images: vec::IntoIter<(Image, &'static [T])>,
+}
+
+impl<T: 'static> Iterator for Images<T> {
+    type Item = (Image, &'static [T]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.images.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.images.size_hint()
+    }
+}
+
+impl<T: 'static> ExactSizeIterator for Images<T> {}
+
+/// Iterator over the elements of a slice in every loaded object.
+///
+/// Returned by [`elements`].
+pub struct Elements<T: 'static> {
+    images: Images<T>,
+    elements: slice::Iter<'static, T>,
+}
+
+impl<T: 'static> Iterator for Elements<T> {
+    type Item = &'static T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(element) = self.elements.next() {
+                return Some(element);
+            }
+            let (_image, elements) = self.images.next()?;
+            self.elements = elements.iter();
+        }
+    }
+}
+
+/// Every loaded object containing elements of the slice, with its elements.
+///
+/// # Safety
+///
+/// Every loaded object that contains the slice's section must hold elements
+/// of type `T` in it, and none of them may be unloaded while the returned
+/// slices are in use.
+pub unsafe fn images<T: 'static>(slice: DistributedSlice<[T]>) -> Images<T> {
+    let own = slice.image();
+    let mut images = Vec::new();
+    for image in loaded_images() {
+        if let Some(elements) = unsafe { elements_in(&image, &own, slice) } {
+            images.push((image, elements));
+        }
+    }
+    Images {
+        images: images.into_iter(),
+    }
+}
+
+/// The elements of the slice in every loaded object, one object after
+/// another.
+///
+/// # Safety
+///
+/// As for [`images`].
+pub unsafe fn elements<T: 'static>(slice: DistributedSlice<[T]>) -> Elements<T> {
+    Elements {
+        images: unsafe { images(slice) },
+        elements: [].iter(),
+    }
+}
+
+/// Reports objects containing elements of a slice as they appear.
+///
+/// The dynamic loader offers no callback when an object is loaded, so the
+/// watcher is polled, for example after loading plugins. Polling is cheap when
+/// nothing was loaded since the previous poll.
+pub struct Watcher<T: 'static> {
+    slice: DistributedSlice<[T]>,
+    seen: HashSet<Image>,
+    adds: Option<u64>,
+}
+
+impl<T: 'static> Watcher<T> {
+    /// Watcher that has not yet reported any object.
+    pub fn new(slice: DistributedSlice<[T]>) -> Self {
+        Watcher {
+            slice,
+            seen: HashSet::new(),
+            adds: None,
+        }
+    }
+
+    /// Objects containing elements of the slice that were loaded since the
+    /// previous poll, or all of them on the first poll.
+    ///
+    /// # Safety
+    ///
+    /// As for [`images`].
+    pub unsafe fn poll(&mut self) -> Images<T> {
+        let adds = load_count();
+        if adds.is_some() && adds == self.adds {
+            return Images {
+                images: Vec::new().into_iter(),
+            };
+        }
+        self.adds = adds;
+
+        let own = self.slice.image();
+        let mut images = Vec::new();
+        for image in loaded_images() {
+            if !self.seen.insert(image.clone()) {
+                continue;
+            }
+            if let Some(elements) = unsafe { elements_in(&image, &own, self.slice) } {
+                images.push((image, elements));
+            }
+        }
+        Images {
+            images: images.into_iter(),
+        }
+    }
+}
+
+/// Elements of a slice linked into loaded objects other than the one holding
+/// the slice, which the slice therefore does not include.
+///
+/// Returned by [`check`].
+#[derive(Clone, Debug)]
+pub struct StrayElements {
+    name: &'static str,
+    image: Image,
+    strays: Vec<(Image, usize)>,
+}
+
+impl StrayElements {
+    /// The object holding the slice, as given by [`DistributedSlice::image`].
+    pub fn image(&self) -> &Image {
+        &self.image
+    }
+
+    /// Every other object containing elements of the slice, with the number
+    /// of elements in it.
+    pub fn strays(&self) -> &[(Image, usize)] {
+        &self.strays
+    }
+}
+
+impl Display for StrayElements {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "distributed slice `{}` in {} does not include",
+            self.name,
+            self.image.path.display(),
+        )?;
+        for (i, (image, len)) in self.strays.iter().enumerate() {
+            let separator = if i == 0 { " " } else { ", " };
+            let elements = if *len == 1 { "element" } else { "elements" };
+            write!(
+                formatter,
+                "{separator}{len} {elements} in {}",
+                image.path.display(),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for StrayElements {}
+
+/// Check that every element of the slice in a loaded object is included in
+/// the slice.
+///
+/// Elements land in a different object from the slice when a shared object
+/// and the executable, or two shared objects, both link in the crates adding
+/// them. The slice only includes those in the object holding it.
+///
+/// # Errors
+///
+/// Returns the objects holding elements that the slice does not include.
+pub fn check<T: 'static>(slice: DistributedSlice<[T]>) -> Result<(), StrayElements> {
+    let image = slice.image();
+    let mut strays = Vec::new();
+    for other in loaded_images() {
+        if other == image {
+            continue;
+        }
+        if let Some((_addr, size)) = find_section(&other, slice) {
+            let len = size / stride::<T>();
+            if len > 0 {
+                strays.push((other, len));
+            }
+        }
+    }
+    if strays.is_empty() {
+        Ok(())
+    } else {
+        Err(StrayElements {
+            name: slice.name(),
+            image,
+            strays,
+        })
+    }
+}
+
+// The loaded object whose memory contains the address.
+pub(crate) fn image_containing(addr: usize) -> Option<Image> {
+    loaded_images()
+        .into_iter()
+        .find(|image| image.contains(addr))
+}
+
+// The elements of the slice in the object. Those in the object holding the
+// slice are exactly the slice's own.
+unsafe fn elements_in<T: 'static>(
+    image: &Image,
+    own: &Image,
+    slice: DistributedSlice<[T]>,
+) -> Option<&'static [T]> {
+    if image == own {
+        return Some(slice.static_slice());
+    }
+
+    let (start, size) = find_section(image, slice)?;
+    let len = size / stride::<T>();
+    let start = if mem::size_of::<T>() == 0 || len == 0 {
+        NonNull::dangling().as_ptr()
+    } else {
+        start as *const T
+    };
+    Some(unsafe { slice::from_raw_parts(start, len) })
+}
+
+// Zero-sized elements are counted by their markers, as in the slice itself.
+// See private::zst_marker_len.
+fn stride<T>() -> usize {
+    if mem::size_of::<T>() == 0 {
+        mem::align_of::<T>()
+    } else {
+        mem::size_of::<T>()
+    }
+}
+
+#[repr(C)]
+struct DlPhdrInfo {
+    addr: usize,
+    name: *const c_char,
+    phdr: *const c_void,
+    phnum: u16,
+    adds: u64,
+    subs: u64,
+}
+
+const RTLD_LAZY: c_int = 0x1;
+const RTLD_NOLOAD: c_int = 0x4;
+
+#[cfg_attr(target_env = "gnu", link(name = "dl"))]
+extern "C" {
+    fn dl_iterate_phdr(
+        callback: unsafe extern "C" fn(*mut DlPhdrInfo, usize, *mut c_void) -> c_int,
+        data: *mut c_void,
+    ) -> c_int;
+    fn dlopen(filename: *const c_char, flags: c_int) -> *mut c_void;
+    fn dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void;
+    fn dlclose(handle: *mut c_void) -> c_int;
+}
+
+fn loaded_images() -> Vec<Image> {
+    unsafe extern "C" fn callback(info: *mut DlPhdrInfo, _size: usize, data: *mut c_void) -> c_int {
+        let images = unsafe { &mut *data.cast::<Vec<Image>>() };
+        let info = unsafe { &*info };
+        let name = if info.name.is_null() {
+            &[][..]
+        } else {
+            unsafe { CStr::from_ptr(info.name) }.to_bytes()
+        };
+        // The executable is reported without a name.
+        let executable = name.is_empty();
+        let path = if executable {
+            PathBuf::from("/proc/self/exe")
+        } else {
+            PathBuf::from(OsStr::from_bytes(name))
+        };
+        let phdrs = unsafe {
+            slice::from_raw_parts(
+                info.phdr.cast::<u8>(),
+                usize::from(info.phnum) * elf::PHDR_SIZE,
+            )
+        };
+        let segments = phdrs
+            .chunks_exact(elf::PHDR_SIZE)
+            .filter(|phdr| u32::from_ne_bytes(field(phdr, elf::P_TYPE)) == PT_LOAD)
+            .map(|phdr| {
+                let start = info.addr.wrapping_add(word(phdr, elf::P_VADDR));
+                start..=start.wrapping_add(word(phdr, elf::P_MEMSZ))
+            })
+            .collect();
+        images.push(Image {
+            path,
+            base: info.addr,
+            executable,
+            segments,
+        });
+        0
+    }
+
+    let mut images = Vec::new();
+    unsafe {
+        dl_iterate_phdr(callback, ptr::addr_of_mut!(images).cast());
+    }
+    images
+}
+
+// Number of objects loaded over the lifetime of the process, if the dynamic
+// loader reports it.
+fn load_count() -> Option<u64> {
+    unsafe extern "C" fn callback(info: *mut DlPhdrInfo, size: usize, data: *mut c_void) -> c_int {
+        let adds = unsafe { &mut *data.cast::<Option<u64>>() };
+        if size >= mem::size_of::<DlPhdrInfo>() {
+            *adds = Some(unsafe { (*info).adds });
+        }
+        // Every object reports the same count, so the first one will do.
+        1
+    }
+
+    let mut adds = None;
+    unsafe {
+        dl_iterate_phdr(callback, ptr::addr_of_mut!(adds).cast());
+    }
+    adds
+}
+
+#[cfg(target_pointer_width = "64")]
+mod elf {
+    pub const PHDR_SIZE: usize = 56;
+    pub const P_TYPE: usize = 0;
+    pub const P_VADDR: usize = 16;
+    pub const P_MEMSZ: usize = 40;
+    pub const WORD: usize = 8;
+}
+
+#[cfg(target_pointer_width = "32")]
+mod elf {
+    pub const PHDR_SIZE: usize = 32;
+    pub const P_TYPE: usize = 0;
+    pub const P_VADDR: usize = 8;
+    pub const P_MEMSZ: usize = 20;
+    pub const WORD: usize = 4;
+}
+
+const PT_LOAD: u32 = 1;
+
+// Address and size of the slice's section in the object's memory, from the
+// `__start_` and `__stop_` symbols that the linker defines for it. A version
+// script hides those, as rustc's does for a cdylib under GNU ld and gold, in
+// which case the bounds exported by the declaration are used instead.
+fn find_section<T>(image: &Image, slice: DistributedSlice<[T]>) -> Option<(usize, usize)> {
+    let handle = if image.executable {
+        unsafe { dlopen(ptr::null(), RTLD_LAZY) }
+    } else {
+        let path = CString::new(image.path.as_os_str().as_bytes()).ok()?;
+        unsafe { dlopen(path.as_ptr(), RTLD_LAZY | RTLD_NOLOAD) }
+    };
+    if handle.is_null() {
+        return None;
+    }
+    let start = symbol(handle, "__start_", slice.elf_section());
+    let stop = symbol(handle, "__stop_", slice.elf_section());
+    let bounds = symbol(handle, "", slice.elf_bounds());
+    unsafe {
+        dlclose(handle);
+    }
+
+    // The lookup continues into the object's dependencies, and for the
+    // executable into every object loaded globally, when the object itself
+    // does not export the symbol.
+    let (start, stop) = match (start, stop) {
+        (Some(start), Some(stop)) if image.contains(start) && image.contains(stop) => (start, stop),
+        _ => {
+            let bounds = bounds.filter(|&bounds| image.contains(bounds))?;
+            let bounds = unsafe { &*(bounds as *const SectionBounds) };
+            (bounds.start as usize, bounds.stop as usize)
+        }
+    };
+    if start <= stop {
+        Some((start, stop - start))
+    } else {
+        None
+    }
+}
+
+fn symbol(handle: *mut c_void, prefix: &str, name: &str) -> Option<usize> {
+    let symbol = CString::new(std::format!("{prefix}{name}")).ok()?;
+    let addr = unsafe { dlsym(handle, symbol.as_ptr()) };
+    if addr.is_null() {
+        None
+    } else {
+        Some(addr as usize)
+    }
+}
+
+fn field<const N: usize>(bytes: &[u8], offset: usize) -> [u8; N] {
+    bytes[offset..offset + N].try_into().unwrap()
+}
+
+fn word(bytes: &[u8], offset: usize) -> usize {
+    usize::from_ne_bytes(field::<{ elf::WORD }>(bytes, offset))
+}
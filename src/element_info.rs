@@ -0,0 +1,141 @@
+use core::fmt::{self, Debug};
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+use core::slice;
+
+/// Source information about one element of a distributed slice.
+///
+/// Recorded for every element of a distributed slice whose declaration opts in
+/// by `#[linkme(element_info)]`, and accessed through
+/// [`DistributedSlice::entries`][crate::DistributedSlice::entries].
+pub struct ElementInfo {
+    element: *const (),
+    name: &'static str,
+    module_path: &'static str,
+    file: &'static str,
+    line: u32,
+    position: Option<usize>,
+}
+
+unsafe impl Send for ElementInfo {}
+
+unsafe impl Sync for ElementInfo {}
+
+impl ElementInfo {
+    #[doc(hidden)]
+    pub const fn private_new(
+        element: *const (),
+        name: &'static str,
+        module_path: &'static str,
+        file: &'static str,
+        line: u32,
+        position: Option<usize>,
+    ) -> Self {
+        ElementInfo {
+            element,
+            name,
+            module_path,
+            file,
+            line,
+            position,
+        }
+    }
+
+    #[cfg(any(feature = "fallback", target_family = "wasm"))]
+    pub(crate) fn element(&self) -> *const () {
+        self.element
+    }
+
+    // The same info for a copy of the element at another address.
+    #[cfg(any(feature = "fallback", target_family = "wasm"))]
+    pub(crate) fn with_element(&self, element: *const ()) -> Self {
+        ElementInfo { element, ..*self }
+    }
+
+    /// Name of the static or function that defined the element.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Path of the module containing the element, as given by
+    /// `module_path!()`.
+    pub fn module_path(&self) -> &'static str {
+        self.module_path
+    }
+
+    /// Source file containing the element, as given by `file!()`.
+    pub fn file(&self) -> &'static str {
+        self.file
+    }
+
+    /// Line of the element's `#[distributed_slice(...)]` attribute.
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// Position given in `#[distributed_slice(SLICE, N)]`, if any.
+    pub fn position(&self) -> Option<usize> {
+        self.position
+    }
+}
+
+impl Debug for ElementInfo {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("ElementInfo")
+            .field("name", &self.name)
+            .field("module_path", &self.module_path)
+            .field("file", &self.file)
+            .field("line", &self.line)
+            .field("position", &self.position)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Iterator over the elements of a distributed slice together with their
+/// [`ElementInfo`].
+///
+/// Returned by [`DistributedSlice::entries`][crate::DistributedSlice::entries].
+pub struct Entries<T: 'static> {
+    infos: slice::Iter<'static, ElementInfo>,
+    marker: PhantomData<fn() -> &'static T>,
+}
+
+impl<T> Entries<T> {
+    pub(crate) fn new(infos: &'static [ElementInfo]) -> Self {
+        Entries {
+            infos: infos.iter(),
+            marker: PhantomData,
+        }
+    }
+
+    fn entry(info: &'static ElementInfo) -> (&'static T, &'static ElementInfo) {
+        // Every ElementInfo in a slice's info section was emitted alongside an
+        // element of that slice, which the element's typecheck guarantees to
+        // have type T.
+        let element = unsafe { &*info.element.cast::<T>() };
+        (element, info)
+    }
+}
+
+impl<T> Iterator for Entries<T> {
+    type Item = (&'static T, &'static ElementInfo);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.infos.next().map(Self::entry)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.infos.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for Entries<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.infos.next_back().map(Self::entry)
+    }
+}
+
+impl<T> ExactSizeIterator for Entries<T> {}
+
+impl<T> FusedIterator for Entries<T> {}
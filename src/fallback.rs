@@ -0,0 +1,144 @@
+//! Registration of elements at runtime, for targets without linker support.
+//!
+//! On WebAssembly, and with linkme's `fallback` feature on any other target
+//! that the linker-based implementation does not support, a distributed slice
+//! keeps its elements in a lock-free intrusive list instead of a linker
+//! section. Each element is pushed onto the list by a constructor that runs
+//! before `main`. The first access to the slice copies the elements into one
+//! allocation, which is the `&'static [T]` handed out from then on.
+
+use crate::element_info::ElementInfo;
+use alloc::vec::Vec;
+use core::hint;
+use core::ptr;
+use core::slice;
+use core::sync::atomic::{AtomicBool, AtomicPtr, AtomicU8, AtomicUsize, Ordering};
+
+const UNINIT: u8 = 0;
+const BUSY: u8 = 1;
+const DONE: u8 = 2;
+
+#[doc(hidden)]
+pub struct Registry {
+    head: AtomicPtr<Node>,
+    state: AtomicU8,
+    elements: AtomicPtr<()>,
+    infos: AtomicPtr<ElementInfo>,
+    len: AtomicUsize,
+}
+
+#[doc(hidden)]
+pub struct Node {
+    info: ElementInfo,
+    next: AtomicPtr<Node>,
+    registered: AtomicBool,
+}
+
+impl Registry {
+    #[doc(hidden)]
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Registry {
+            head: AtomicPtr::new(ptr::null_mut()),
+            state: AtomicU8::new(UNINIT),
+            elements: AtomicPtr::new(ptr::null_mut()),
+            infos: AtomicPtr::new(ptr::null_mut()),
+            len: AtomicUsize::new(0),
+        }
+    }
+
+    pub(crate) fn register(&self, node: &'static Node) {
+        if node.registered.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let node_ptr = (node as *const Node).cast_mut();
+        let mut head = self.head.load(Ordering::Relaxed);
+        loop {
+            node.next.store(head, Ordering::Relaxed);
+            match self.head.compare_exchange_weak(
+                head,
+                node_ptr,
+                Ordering::Release,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return,
+                Err(current) => head = current,
+            }
+        }
+    }
+
+    // The elements registered so far, copied into one allocation on the first
+    // call, and the info of each pointing at its copy. Elements are ordered by
+    // their position, if any, as the PE/COFF linker orders them, and otherwise
+    // in registration order.
+    //
+    // Safety: every registered node's element has type T.
+    pub(crate) unsafe fn materialize<T: 'static>(&self) -> (&'static [T], &'static [ElementInfo]) {
+        loop {
+            match self
+                .state
+                .compare_exchange(UNINIT, BUSY, Ordering::Acquire, Ordering::Acquire)
+            {
+                Ok(_) => {
+                    unsafe { self.copy_elements::<T>() };
+                    self.state.store(DONE, Ordering::Release);
+                    break;
+                }
+                Err(DONE) => break,
+                Err(_) => hint::spin_loop(),
+            }
+        }
+
+        let len = self.len.load(Ordering::Relaxed);
+        let elements = self.elements.load(Ordering::Relaxed).cast::<T>();
+        let infos = self.infos.load(Ordering::Relaxed);
+        unsafe {
+            (
+                slice::from_raw_parts(elements, len),
+                slice::from_raw_parts(infos, len),
+            )
+        }
+    }
+
+    unsafe fn copy_elements<T: 'static>(&self) {
+        let mut nodes = Vec::new();
+        let mut node = self.head.load(Ordering::Acquire);
+        while let Some(current) = unsafe { node.as_ref() } {
+            nodes.push(current);
+            node = current.next.load(Ordering::Relaxed);
+        }
+        nodes.reverse();
+        nodes.sort_by_key(|node| node.info.position());
+
+        // The copies are never dropped, like the statics they are copied from.
+        let mut elements = Vec::with_capacity(nodes.len());
+        for node in &nodes {
+            elements.push(unsafe { ptr::read(node.info.element().cast::<T>()) });
+        }
+        let elements: &'static [T] = elements.leak();
+
+        let mut infos = Vec::with_capacity(nodes.len());
+        for (node, element) in nodes.iter().zip(elements) {
+            let element = (element as *const T).cast::<()>();
+            infos.push(node.info.with_element(element));
+        }
+        let infos: &'static [ElementInfo] = infos.leak();
+
+        let elements = elements.as_ptr().cast_mut();
+        self.elements.store(elements.cast(), Ordering::Relaxed);
+        self.infos
+            .store(infos.as_ptr().cast_mut(), Ordering::Relaxed);
+        self.len.store(nodes.len(), Ordering::Relaxed);
+    }
+}
+
+impl Node {
+    #[doc(hidden)]
+    pub const fn new(info: ElementInfo) -> Self {
+        Node {
+            info,
+            next: AtomicPtr::new(ptr::null_mut()),
+            registered: AtomicBool::new(false),
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use crate::element_info::ElementInfo;
+use core::fmt::{self, Display};
+
+// An expectation by force_link!(krate => SLICE) that a crate contributes to
+// the slice, kept in a section of the slice's own, so that only declarations
+// with #[linkme(force_link)] refer to any such section.
+#[doc(hidden)]
+pub struct ForceLinked {
+    krate: &'static str,
+}
+
+impl ForceLinked {
+    #[doc(hidden)]
+    pub const fn private_new(krate: &'static str) -> Self {
+        ForceLinked { krate }
+    }
+}
+
+// Crates expected by force_link! to contribute to a slice that contributed
+// none of its elements, each listed once. A crate is recognized by the first
+// segment of the module path of the elements it contributed.
+pub(crate) struct Missing {
+    expectations: &'static [ForceLinked],
+    infos: &'static [ElementInfo],
+}
+
+impl Missing {
+    pub(crate) fn new(expectations: &'static [ForceLinked], infos: &'static [ElementInfo]) -> Self {
+        Missing {
+            expectations,
+            infos,
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.expectations
+            .iter()
+            .enumerate()
+            .filter(move |&(i, expectation)| {
+                !self.expectations[..i]
+                    .iter()
+                    .any(|earlier| earlier.krate == expectation.krate)
+                    && !self.infos.iter().any(|info| {
+                        let krate = info.module_path().split("::").next();
+                        krate == Some(expectation.krate)
+                    })
+            })
+            .map(|(_i, expectation)| expectation.krate)
+    }
+}
+
+impl Display for Missing {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        for (i, krate) in self.iter().enumerate() {
+            if i > 0 {
+                formatter.write_str(", ")?;
+            }
+            formatter.write_str(krate)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+#[cfg(target_family = "wasm")]
+use core::sync::atomic::{AtomicBool, Ordering};
+
+// Whether the constructors have run, set by the first call to init or by
+// linkme's own constructor below when something else ran them first.
+#[cfg(target_family = "wasm")]
+static INITIALIZED: AtomicBool = AtomicBool::new(false);
+
+#[cfg(target_family = "wasm")]
+#[used]
+#[link_section = ".init_array"]
+static CONSTRUCTOR: extern "C" fn() = {
+    extern "C" fn constructor() {
+        INITIALIZED.store(true, Ordering::Release);
+    }
+    constructor
+};
+
+/// Run the constructors that register elements of distributed slices, on
+/// targets where nothing else runs them.
+///
+/// Only WebAssembly needs this, and only a module in which nothing calls
+/// `__wasm_call_ctors`, such as a library instantiated without wasm-bindgen.
+/// It must then be called before the first access to a distributed slice.
+/// The constructors run at most once, however many times this is called and
+/// whether or not the module has any elements. On every other target this
+/// does nothing.
+pub fn init() {
+    #[cfg(target_family = "wasm")]
+    {
+        extern "C" {
+            fn __wasm_call_ctors();
+        }
+
+        if !INITIALIZED.swap(true, Ordering::AcqRel) {
+            unsafe { __wasm_call_ctors() }
+        }
+    }
+}
@@ -12,12 +12,19 @@
 //!
 //! # Platform support
 //!
-//! | Component | Linux | macOS | Windows | FreeBSD | OpenBSD | illumos | Other...<sup>†</sup> |
-//! |:---|:---:|:---:|:---:|:---:|:---:|:---:|:---:|
-//! | Distributed slice | 💚 | 💚 | 💚 | 💚 | 💚 | 💚 | |
+//! | Component | Linux | macOS | Windows | FreeBSD | OpenBSD | illumos | WebAssembly<sup>*</sup> | Other...<sup>†</sup> |
+//! |:---|:---:|:---:|:---:|:---:|:---:|:---:|:---:|:---:|
+//! | Distributed slice | 💚 | 💚 | 💚 | 💚 | 💚 | 💚 | 💚 | |
+//! | Distributed map | 💚 | 💚 | 💚 | 💚 | 💚 | 💚 | 💚 | |
+//! | Distributed slot | 💚 | 💚 | 💚 | 💚 | 💚 | 💚 | 💚 | |
+//!
+//! <br>***<sup>*</sup>*** On WebAssembly, elements are registered at runtime
+//! instead of through the linker; see [Fallback
+//! backend][DistributedSlice#fallback-backend].
 //!
 //! <br>***<sup>†</sup>*** We welcome PRs adding support for any platforms not
-//! listed here.
+//! listed here. On other platforms, linkme's `fallback` feature registers
+//! elements at runtime the same way as on WebAssembly.
 //!
 //! <br>
 //!
@@ -132,6 +139,65 @@
 //! # };
 //! ```
 //! </details>
+//!
+//! <br>
+//!
+//! # Distributed map
+//!
+//! A distributed map is a collection of static values registered under const
+//! keys from anywhere in the dependency graph, built on the same linker support
+//! as the distributed slice. Refer to
+//! [`linkme::DistributedMap`][DistributedMap] for details.
+//!
+//! ```
+//! # #![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+//! #
+//! use linkme::distributed_map;
+//!
+//! #[distributed_map]
+//! pub static ROUTES: Map<&str, fn() -> u16>;
+//!
+//! #[distributed_map(ROUTES, key = "/health")]
+//! fn health() -> u16 {
+//!     200
+//! }
+//!
+//! fn main() {
+//!     if let Some(route) = ROUTES.get("/health") {
+//!         assert_eq!(route(), 200);
+//!     }
+//! }
+//! ```
+//!
+//! <br>
+//!
+//! # Distributed slot
+//!
+//! A distributed slot is a static with exactly one definition, which may come
+//! from any crate in the dependency graph. A program that links zero
+//! definitions or more than one fails to link. A slot may alternatively be
+//! declared with a default value that at most one crate overrides. Refer to
+//! [`linkme::DistributedSlot`][DistributedSlot] for details.
+//!
+//! ```
+//! # #![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+//! #
+//! use linkme::distributed_slot;
+//!
+//! #[distributed_slot]
+//! pub static CLOCK: fn() -> u64;
+//!
+//! #[distributed_slot(CLOCK)]
+//! fn monotonic_clock() -> u64 {
+//!     /* ... */
+//! #   0
+//! }
+//!
+//! fn main() {
+//!     let now = CLOCK();
+//! #   let _ = now;
+//! }
+//! ```
 
 #![no_std]
 #![doc(html_root_url = "https://docs.rs/linkme/0.3.36")]
@@ -147,11 +213,31 @@
     clippy::unused_self
 )]
 
+#[cfg(any(feature = "alloc", feature = "fallback", target_family = "wasm"))]
+extern crate alloc;
+#[cfg(any(feature = "build", feature = "dynamic"))]
+extern crate std;
+
+#[cfg(feature = "build")]
+pub mod build;
+mod distributed_map;
 mod distributed_slice;
+mod distributed_slot;
+#[cfg(all(feature = "dynamic", target_os = "linux"))]
+pub mod dynamic;
+mod element_info;
+#[cfg(any(feature = "fallback", target_family = "wasm"))]
+mod fallback;
+mod force_link;
+mod init;
 mod private;
 
 include!(concat!(env!("OUT_DIR"), "/private.rs"));
 
 pub use linkme_impl::*;
 
+pub use crate::distributed_map::{DistributedMap, MapIter};
 pub use crate::distributed_slice::DistributedSlice;
+pub use crate::distributed_slot::DistributedSlot;
+pub use crate::element_info::{ElementInfo, Entries};
+pub use crate::init::init;
@@ -1,7 +1,31 @@
+use core::mem;
+
+#[doc(hidden)]
+pub use crate::distributed_map::{MapEntry, MapIndex};
+#[cfg(any(feature = "fallback", target_family = "wasm"))]
+#[doc(hidden)]
+pub use crate::fallback::{Node, Registry};
+#[doc(hidden)]
+pub use crate::force_link::*;
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub use alloc::boxed::Box;
 #[doc(hidden)]
-pub use core::primitive::isize;
+pub use core::future::Future;
+#[doc(hidden)]
+pub use core::marker::Send;
+#[doc(hidden)]
+pub use core::option::Option;
+#[doc(hidden)]
+pub use core::pin::Pin;
+#[doc(hidden)]
+pub use core::primitive::{isize, usize};
 #[doc(hidden)]
 pub use core::ptr;
+#[doc(hidden)]
+pub use core::sync::atomic::AtomicBool;
+#[doc(hidden)]
+pub use core::{file, line, module_path};
 
 #[doc(hidden)]
 pub trait Slice {
@@ -15,6 +39,38 @@ impl<T> Slice for [T] {
 #[doc(hidden)]
 pub enum Void {}
 
+// Length in bytes of the marker placed in a distributed slice's section next to
+// each element of type T. Only zero-sized elements need one.
+#[doc(hidden)]
+pub const fn zst_marker_len<T>() -> usize {
+    if mem::size_of::<T>() == 0 {
+        mem::align_of::<T>()
+    } else {
+        0
+    }
+}
+
+// Exported by a declaration under linkme::dynamic's own symbol for the
+// section's bounds. See dynamic::find_section.
+#[cfg(all(feature = "dynamic", target_os = "linux"))]
+#[doc(hidden)]
+#[repr(C)]
+pub struct SectionBounds {
+    pub(crate) start: *const u8,
+    pub(crate) stop: *const u8,
+}
+
+#[cfg(all(feature = "dynamic", target_os = "linux"))]
+unsafe impl Sync for SectionBounds {}
+
+#[cfg(all(feature = "dynamic", target_os = "linux"))]
+impl SectionBounds {
+    #[doc(hidden)]
+    pub const fn new(start: *const u8, stop: *const u8) -> Self {
+        SectionBounds { start, stop }
+    }
+}
+
 #[cfg(any(target_os = "uefi", target_os = "windows"))]
 #[doc(hidden)]
 pub type BoundaryElement<T> = core::mem::MaybeUninit<<T as Slice>::Element>;
@@ -0,0 +1,62 @@
+#![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+#![allow(unknown_lints, non_local_definitions)] // FIXME
+
+use linkme::{distributed_slice, distributed_slice_element};
+
+#[distributed_slice]
+pub static HOOKS: [fn() -> i32];
+
+macro_rules! hooks {
+    ($($n:literal)*) => {
+        $(
+            distributed_slice_element!(HOOKS => || $n);
+        )*
+    };
+}
+
+hooks!(1 2 3);
+
+distributed_slice_element!(HOOKS => || 4);
+
+mod nested {
+    use linkme::distributed_slice_element;
+
+    distributed_slice_element!(super::HOOKS => || 5);
+}
+
+#[test]
+fn test_anonymous() {
+    let mut results = HOOKS.iter().map(|hook| hook()).collect::<Vec<_>>();
+    results.sort_unstable();
+    assert_eq!(results, [1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn test_anonymous_static() {
+    #[distributed_slice]
+    static NAMES: [&str];
+
+    distributed_slice_element!(NAMES => "a");
+    distributed_slice_element!(NAMES => "b");
+
+    let mut names = NAMES.to_vec();
+    names.sort_unstable();
+    assert_eq!(names, ["a", "b"]);
+}
+
+// The slice's name remains available in the type namespace.
+#[allow(non_camel_case_types, dead_code, clippy::upper_case_acronyms)]
+struct LABELS {
+    label: &'static str,
+}
+
+#[distributed_slice]
+static LABELS: [&str];
+
+distributed_slice_element!(LABELS => "label");
+
+#[test]
+fn test_name_in_type_namespace() {
+    let labels = LABELS { label: "label" };
+    assert_eq!(LABELS[..], [labels.label]);
+}
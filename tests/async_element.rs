@@ -0,0 +1,83 @@
+#![cfg(feature = "alloc")]
+#![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+#![allow(clippy::unused_async)]
+
+use linkme::distributed_slice;
+use std::future::Future;
+use std::pin::{pin, Pin};
+use std::ptr;
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+type Job = fn(u32) -> Pin<Box<dyn Future<Output = u32> + Send>>;
+
+#[distributed_slice]
+pub static JOBS: [Job];
+
+#[distributed_slice(JOBS)]
+async fn increment(n: u32) -> u32 {
+    ready(n + 1).await
+}
+
+#[distributed_slice]
+pub static ENTRY_POINTS: [fn() -> Pin<Box<dyn Future<Output = ()> + Send>>];
+
+#[distributed_slice(ENTRY_POINTS)]
+async fn entry_point() {}
+
+pub struct Request {
+    path: &'static str,
+}
+
+type Handler = for<'a> fn(&'a Request, &'a str) -> Pin<Box<dyn Future<Output = usize> + Send + 'a>>;
+
+#[distributed_slice]
+pub static HANDLERS: [Handler];
+
+#[distributed_slice(HANDLERS)]
+async fn handle(req: &Request, prefix: &str) -> usize {
+    ready(req.path.strip_prefix(prefix).unwrap_or(req.path))
+        .await
+        .len()
+}
+
+async fn ready<T>(value: T) -> T {
+    value
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    const VTABLE: RawWakerVTable = RawWakerVTable::new(
+        |_| RawWaker::new(ptr::null(), &VTABLE),
+        |_| {},
+        |_| {},
+        |_| {},
+    );
+    let waker = unsafe { Waker::from_raw(RawWaker::new(ptr::null(), &VTABLE)) };
+    let mut cx = Context::from_waker(&waker);
+    let mut future = pin!(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+#[test]
+fn test_async() {
+    assert_eq!(JOBS.len(), 1);
+    assert_eq!(block_on(JOBS[0](41)), 42);
+    assert_eq!(block_on(increment(1)), 2);
+}
+
+#[test]
+fn test_no_args() {
+    assert_eq!(ENTRY_POINTS.len(), 1);
+    block_on(ENTRY_POINTS[0]());
+}
+
+#[test]
+fn test_borrowed_args() {
+    let req = Request { path: "/api/users" };
+    let prefix = String::from("/api");
+    assert_eq!(HANDLERS.len(), 1);
+    assert_eq!(block_on(HANDLERS[0](&req, &prefix)), 6);
+}
@@ -0,0 +1,21 @@
+use linkme::build::{CHeader, Slice};
+use std::env;
+use std::path::PathBuf;
+
+fn main() {
+    println!("cargo:rerun-if-changed=elements.c");
+
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    CHeader::new()
+        .slice(Slice::new("COMMANDS", "struct command", 16, 8))
+        .slice(Slice::new("CODES", "uint32_t", 4, 4).section("linkme_c_codes"))
+        .slice(Slice::new("FLAGS", "uint32_t", 4, 4))
+        .write_to_file(out_dir.join("linkme.h"))
+        .unwrap();
+
+    cc::Build::new()
+        .file("elements.c")
+        .include(&out_dir)
+        .link_lib_modifier("+whole-archive")
+        .compile("elements");
+}
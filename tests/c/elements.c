@@ -0,0 +1,16 @@
+#include <stdint.h>
+
+struct command {
+    const char *name;
+    uint64_t code;
+};
+
+#include "linkme.h"
+
+LINKME_ELEMENT(COMMANDS, STATUS) = {"status", 1};
+LINKME_ELEMENT(COMMANDS, COMMIT) = {"commit", 2};
+
+LINKME_ELEMENT(CODES, NOT_FOUND) = 404;
+
+LINKME_ELEMENT(FLAGS, VERBOSE) = 1;
+LINKME_ELEMENT(FLAGS, QUIET) = 2;
@@ -0,0 +1,61 @@
+#![cfg(test)]
+
+use linkme::distributed_slice;
+use std::ffi::{c_char, CStr};
+
+#[repr(C)]
+pub struct Command {
+    name: *const c_char,
+    code: u64,
+}
+
+unsafe impl Sync for Command {}
+
+// Layout asserted by the generated header against struct command in
+// elements.c, with the size and alignment given in build.rs.
+#[distributed_slice]
+pub static COMMANDS: [Command];
+
+#[distributed_slice(COMMANDS)]
+static PUSH: Command = Command {
+    name: c"push".as_ptr(),
+    code: 3,
+};
+
+// Elements only from C, which satisfy min = 1 at link time.
+#[distributed_slice(min = 1)]
+pub static FLAGS: [u32];
+
+#[distributed_slice]
+#[linkme(section = "linkme_c_codes")]
+pub static CODES: [u32];
+
+#[distributed_slice(CODES)]
+static OK: u32 = 200;
+
+#[test]
+fn test_c_elements() {
+    let mut commands = COMMANDS
+        .iter()
+        .map(|command| {
+            let name = unsafe { CStr::from_ptr(command.name) };
+            (command.code, name.to_str().unwrap())
+        })
+        .collect::<Vec<_>>();
+    commands.sort_unstable();
+    assert_eq!(commands, [(1, "status"), (2, "commit"), (3, "push")]);
+}
+
+#[test]
+fn test_section_name() {
+    let mut codes = CODES.to_vec();
+    codes.sort_unstable();
+    assert_eq!(codes, [200, 404]);
+}
+
+#[test]
+fn test_c_only() {
+    let mut flags = FLAGS.to_vec();
+    flags.sort_unstable();
+    assert_eq!(flags, [1, 2]);
+}
@@ -0,0 +1,69 @@
+#![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+
+use linkme::distributed_slice;
+
+#[distributed_slice(min = 1)]
+static BACKENDS: [&str];
+
+#[distributed_slice(BACKENDS)]
+static MEMORY: &str = "memory";
+
+#[distributed_slice(BACKENDS)]
+static DISK: &str = "disk";
+
+#[distributed_slice(exact = 2)]
+static PAIR: [i32];
+
+#[distributed_slice(PAIR)]
+static LEFT: i32 = 1;
+
+#[distributed_slice(PAIR)]
+static RIGHT: i32 = 2;
+
+#[distributed_slice(min = 1, max = 2)]
+static AT_MOST_TWO: [i32];
+
+#[distributed_slice(AT_MOST_TWO)]
+static FIRST: i32 = 1;
+
+#[distributed_slice(AT_MOST_TWO)]
+static SECOND: i32 = 2;
+
+#[distributed_slice(AT_MOST_TWO)]
+static THIRD: i32 = 3;
+
+// A maximum of one is enforced by the linker, see tests/link_error.
+#[distributed_slice(exact = 1)]
+static SINGLETON: [i32];
+
+#[distributed_slice(SINGLETON)]
+static ONE: i32 = 1;
+
+#[distributed_slice(min = 3)]
+static TRIPLE: [i32];
+
+#[distributed_slice(TRIPLE)]
+static ONLY: i32 = 1;
+
+#[distributed_slice(max = 4)]
+static EMPTY: [i32];
+
+#[test]
+fn test_satisfied() {
+    assert_eq!(BACKENDS.len(), 2);
+    assert_eq!(PAIR.len(), 2);
+    assert_eq!(SINGLETON[..], [1]);
+    assert!(EMPTY.is_empty());
+}
+
+#[test]
+#[should_panic = "#[distributed_slice] with name \"AT_MOST_TWO\" has 3 elements, expected at most 2"]
+fn test_too_many() {
+    let _ = AT_MOST_TWO.len();
+}
+
+#[test]
+#[should_panic = "#[distributed_slice] with name \"TRIPLE\" has 1 element, expected at least 3"]
+fn test_too_few() {
+    let _ = TRIPLE.len();
+}
@@ -1,7 +1,10 @@
 #[rustversion::attr(not(nightly), ignore = "requires nightly")]
 #[cfg_attr(miri, ignore = "incompatible with miri")]
+#[cfg(not(linkme_fallback))] // expectations are for the linker-based backend
 #[test]
 fn ui() {
     let t = trybuild::TestCases::new();
     t.compile_fail("tests/ui/*.rs");
+    #[cfg(feature = "alloc")]
+    t.compile_fail("tests/ui/alloc/*.rs");
 }
@@ -1,3 +1,4 @@
+use linkme::build::LinkerScript;
 use std::env;
 use std::fs::File;
 use std::io::Write;
@@ -12,6 +13,17 @@ fn main() {
         .unwrap();
     println!("cargo:rustc-link-search={}", out.display());
 
+    // Keep the slices' sections in flash, when building for the device rather
+    // than checking the workspace on the host
+    LinkerScript::new()
+        .slice("SHENANIGANS")
+        .slice("EMPTY")
+        .write_to_file(out.join("linkme.x"))
+        .unwrap();
+    if env::var("CARGO_CFG_TARGET_OS").unwrap() == "none" {
+        println!("cargo:rustc-link-arg=-Tlinkme.x");
+    }
+
     // Only re-run the build script when memory.x is changed,
     // instead of when any part of the source code changes.
     println!("cargo:rerun-if-changed=memory.x");
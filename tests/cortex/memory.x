@@ -3,11 +3,3 @@ MEMORY
   FLASH : ORIGIN = 0x00000000, LENGTH = 256K
   RAM : ORIGIN = 0x20000000, LENGTH = 64K
 }
-
-SECTIONS {
-  linkme_SHENANIGANS : { *(linkme_SHENANIGANS) } > FLASH
-  linkm2_SHENANIGANS : { *(linkm2_SHENANIGANS) } > FLASH
-  linkme_EMPTY : { *(linkme_EMPTY) } > FLASH
-  linkm2_EMPTY : { *(linkm2_EMPTY) } > FLASH
-}
-INSERT AFTER .rodata
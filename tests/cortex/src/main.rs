@@ -10,7 +10,8 @@ use cortex_m_semihosting::{debug, hprintln};
 
 use linkme::distributed_slice;
 
-#[distributed_slice]
+#[distributed_slice(min = 1)]
+#[linkme(element_info)]
 static SHENANIGANS: [i32];
 
 #[distributed_slice(SHENANIGANS)]
@@ -33,6 +34,8 @@ fn main() -> ! {
 
     assert_eq!(sum, 9 + 99 + 999);
 
+    assert_eq!(SHENANIGANS.entries().unwrap().count(), 3);
+
     #[distributed_slice]
     static EMPTY: [i32] = [..];
 
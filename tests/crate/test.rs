@@ -1 +1,7 @@
 pub use linkme::*;
+
+#[distributed_slice]
+pub static SHARED: [&str];
+
+#[distributed_slice(SHARED)]
+static MAIN: &str = "main";
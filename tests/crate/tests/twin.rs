@@ -0,0 +1,5 @@
+#[test]
+fn test_same_name_and_version() {
+    assert_eq!(linkme_test::SHARED[..], ["main"]);
+    assert_eq!(twin::SHARED[..], ["twin"]);
+}
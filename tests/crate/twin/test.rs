@@ -0,0 +1,11 @@
+// A second crate named linkme_test at the same version as the one in the
+// parent directory, from a different package, declaring a slice of the same
+// name. The two slices must not share a section.
+
+use linkme::distributed_slice;
+
+#[distributed_slice]
+pub static SHARED: [&str];
+
+#[distributed_slice(SHARED)]
+static TWIN: &str = "twin";
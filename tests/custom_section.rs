@@ -0,0 +1,73 @@
+#![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+
+use linkme::{distributed_slice, distributed_slot};
+
+#[distributed_slice]
+#[linkme(section = "lmshapes")]
+pub static SHAPES: [&str];
+
+#[distributed_slice(SHAPES)]
+static CIRCLE: &str = "circle";
+
+#[distributed_slice(SHAPES)]
+static SQUARE: &str = "square";
+
+#[distributed_slice(SHAPES)]
+static TRIANGLE: &str = "triangle";
+
+#[distributed_slot]
+#[linkme(section = "lmport")]
+pub static PORT: u16 = 80;
+
+#[distributed_slot(PORT, override)]
+static HTTPS: u16 = 443;
+
+#[test]
+fn test_elements() {
+    let mut shapes = SHAPES.to_vec();
+    shapes.sort_unstable();
+    assert_eq!(shapes, ["circle", "square", "triangle"]);
+}
+
+#[test]
+fn test_slot() {
+    assert!(PORT.is_overridden());
+    assert_eq!(*PORT.get(), 443);
+}
+
+#[cfg(all(
+    any(target_os = "linux", target_os = "android", target_os = "freebsd"),
+    not(linkme_fallback),
+))]
+#[test]
+#[allow(unused_unsafe)] // addr_of on an extern static is unsafe before Rust 1.82
+fn test_section_name() {
+    extern "Rust" {
+        static __start_lmshapes: [&'static str; 0];
+        static __stop_lmshapes: [&'static str; 0];
+    }
+
+    let start = unsafe { core::ptr::addr_of!(__start_lmshapes) }.cast::<&str>();
+    let stop = unsafe { core::ptr::addr_of!(__stop_lmshapes) }.cast::<&str>();
+    assert_eq!(SHAPES.as_ptr(), start);
+    assert_eq!(unsafe { stop.offset_from(start) }, 3);
+}
+
+// The sections keep the overridden names in the linked executable, where the
+// PE/COFF linker has merged the grouped `$` sections under the name before the
+// `$`.
+#[cfg(not(linkme_fallback))]
+#[test]
+fn test_object_sections() {
+    use object::{Object as _, ObjectSection as _};
+    use std::{env, fs, mem};
+
+    let data = fs::read(env::current_exe().unwrap()).unwrap();
+    let file = object::File::parse(&*data).unwrap();
+
+    let shapes = file.section_by_name("lmshapes").unwrap();
+    assert!(shapes.size() >= 3 * mem::size_of::<&str>() as u64);
+
+    let port = file.section_by_name("lmport").unwrap();
+    assert!(port.size() >= mem::size_of::<u16>() as u64);
+}
@@ -0,0 +1,85 @@
+#![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+
+use linkme::distributed_map;
+use once_cell::sync::Lazy;
+
+#[distributed_map]
+static ROUTES: Map<&str, fn() -> u16>;
+
+#[distributed_map(ROUTES, key = "/health")]
+fn health() -> u16 {
+    200
+}
+
+#[distributed_map(ROUTES, key = "/teapot")]
+static TEAPOT: fn() -> u16 = || 418;
+
+mod nested {
+    use linkme::distributed_map;
+
+    #[distributed_map(super::ROUTES, key = "/nested")]
+    pub(crate) fn nested() -> u16 {
+        204
+    }
+}
+
+#[test]
+fn test_get() {
+    assert_eq!(ROUTES.len(), 3);
+    assert_eq!(ROUTES.get("/health").map(|route| route()), Some(200));
+    assert_eq!(ROUTES.get("/teapot").map(|route| route()), Some(418));
+    assert_eq!(ROUTES.get("/nested").map(|route| route()), Some(204));
+    assert!(ROUTES.get("/missing").is_none());
+    assert!(ROUTES.contains_key("/health"));
+    assert!(!ROUTES.contains_key("/missing"));
+
+    // The original items remain usable.
+    assert_eq!(health(), 200);
+    assert_eq!(TEAPOT(), 418);
+    assert_eq!(nested::nested(), 204);
+}
+
+#[test]
+fn test_iter() {
+    let mut keys: Vec<&str> = ROUTES.iter().map(|(key, _value)| *key).collect();
+    keys.sort_unstable();
+    assert_eq!(keys, ["/health", "/nested", "/teapot"]);
+}
+
+#[distributed_map]
+static EMPTY: Map<u32, u32>;
+
+#[test]
+fn test_empty() {
+    assert!(EMPTY.is_empty());
+    assert!(EMPTY.get(&0).is_none());
+}
+
+#[distributed_map]
+static LAZY: Map<u8, Lazy<i32>>;
+
+#[distributed_map(LAZY, key = 1)]
+static ELEMENT: Lazy<i32> = Lazy::new(|| -1);
+
+#[test]
+fn test_interior_mutable() {
+    assert_eq!(**LAZY.get(&1).unwrap(), -1);
+}
+
+#[distributed_map]
+static DUPLICATE: Map<u8, u8>;
+
+#[distributed_map(DUPLICATE, key = 1)]
+static SECOND: u8 = 2;
+
+#[distributed_map(DUPLICATE, key = 2)]
+static UNIQUE: u8 = 3;
+
+#[distributed_map(DUPLICATE, key = 1)]
+static FIRST: u8 = 1;
+
+#[test]
+#[should_panic = "duplicate key in #[distributed_map] with name \"DUPLICATE\": distributed_map::FIRST at tests/distributed_map.rs:78 and distributed_map::SECOND at tests/distributed_map.rs:72"]
+fn test_duplicate_key() {
+    let _ = DUPLICATE.get(&2);
+}
@@ -0,0 +1,58 @@
+#![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+
+use linkme::distributed_slot;
+use std::ptr;
+
+#[distributed_slot]
+static CLOCK: fn() -> u64;
+
+#[distributed_slot(CLOCK)]
+fn fixed_clock() -> u64 {
+    1_000
+}
+
+#[distributed_slot]
+static GREETING: &str;
+
+#[distributed_slot(GREETING)]
+static HELLO: &str = "hello";
+
+#[distributed_slot]
+static RETRIES: u32 = 3;
+
+#[distributed_slot]
+static TIMEOUT: u32 = 30;
+
+#[distributed_slot(TIMEOUT, override)]
+static LONG_TIMEOUT: u32 = 300;
+
+#[test]
+fn test_fn_slot() {
+    assert_eq!(CLOCK(), 1_000);
+    assert_eq!(CLOCK.name(), "CLOCK");
+}
+
+#[test]
+fn test_static_slot() {
+    // The slot resolves to the one definition itself rather than a copy.
+    let greeting: &'static &str = GREETING.get();
+    assert!(ptr::eq(greeting, &HELLO));
+    assert!(ptr::eq(&*GREETING, &HELLO));
+    assert_eq!(format!("{GREETING:?}"), "\"hello\"");
+}
+
+#[test]
+fn test_default_slot() {
+    assert_eq!(*RETRIES, 3);
+    assert!(!RETRIES.is_overridden());
+    assert!(!CLOCK.is_overridden());
+}
+
+#[test]
+fn test_overridden_slot() {
+    // The fallback backend reads overrides out into a slice of copies.
+    #[cfg(not(linkme_fallback))]
+    assert!(ptr::eq(TIMEOUT.get(), &LONG_TIMEOUT));
+    assert_eq!(*TIMEOUT, 300);
+    assert!(TIMEOUT.is_overridden());
+}
@@ -0,0 +1,32 @@
+use linkme::build::{CHeader, Slice};
+use std::env;
+use std::path::PathBuf;
+
+fn main() {
+    println!("cargo:rerun-if-changed=plugin.c");
+
+    if env::var("CARGO_CFG_TARGET_OS").unwrap() != "linux" {
+        return;
+    }
+
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    CHeader::new()
+        .slice(Slice::new("NUMBERS", "uint32_t", 4, 4))
+        .write_to_file(out_dir.join("linkme.h"))
+        .unwrap();
+
+    // A shared object for the test to dlopen, standing in for a plugin.
+    let compiler = cc::Build::new().get_compiler();
+    let status = compiler
+        .to_command()
+        .arg("-shared")
+        .arg("-fPIC")
+        .arg("-I")
+        .arg(&out_dir)
+        .arg("-o")
+        .arg(out_dir.join("libplugin.so"))
+        .arg("plugin.c")
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
@@ -0,0 +1,9 @@
+// Declares the slice for the test and for the Rust plugin, which each link
+// their own copy of this crate.
+
+#![cfg(target_os = "linux")]
+
+use linkme::distributed_slice;
+
+#[distributed_slice]
+pub static NUMBERS: [u32];
@@ -0,0 +1,14 @@
+#include <stddef.h>
+#include <stdint.h>
+#include "linkme.h"
+
+LINKME_ELEMENT(NUMBERS, TEN) = 10;
+LINKME_ELEMENT(NUMBERS, TWENTY) = 20;
+
+/* The bounds that a declaration of NUMBERS inside this object would see. */
+extern const uint32_t numbers_start[] __asm__("__start_" LINKME_SECTION_NUMBERS);
+extern const uint32_t numbers_stop[] __asm__("__stop_" LINKME_SECTION_NUMBERS);
+
+size_t plugin_numbers_len(void) {
+    return numbers_stop - numbers_start;
+}
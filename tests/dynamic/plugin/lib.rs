@@ -0,0 +1,40 @@
+// A plugin written in Rust, which ../test.rs builds and loads with dlopen.
+// Nothing references its elements, and rustc's version script exports only
+// #[no_mangle] items, yet the slice seen from inside the plugin holds exactly
+// the plugin's elements and the host finds them too.
+
+#![cfg(target_os = "linux")]
+
+use linkme::distributed_slice;
+use linkme::dynamic;
+use linkme_dynamic_test::NUMBERS;
+
+#[distributed_slice(NUMBERS)]
+static HUNDRED: u32 = 100;
+
+#[distributed_slice(NUMBERS)]
+static TWO_HUNDRED: u32 = 200;
+
+#[no_mangle]
+pub extern "C" fn rust_plugin_numbers_sum() -> u32 {
+    NUMBERS.iter().sum()
+}
+
+#[no_mangle]
+pub extern "C" fn rust_plugin_numbers_len() -> usize {
+    NUMBERS.len()
+}
+
+#[no_mangle]
+pub extern "C" fn rust_plugin_image_base() -> usize {
+    NUMBERS.image().base()
+}
+
+// Number of other objects holding elements of the slice.
+#[no_mangle]
+pub extern "C" fn rust_plugin_strays() -> usize {
+    match dynamic::check(NUMBERS) {
+        Ok(()) => 0,
+        Err(error) => error.strays().len(),
+    }
+}
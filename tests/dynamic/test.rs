@@ -0,0 +1,141 @@
+#![cfg(all(test, target_os = "linux"))]
+
+use linkme::distributed_slice;
+use linkme::dynamic::{self, Watcher};
+use linkme_dynamic_test::NUMBERS;
+use std::env;
+use std::ffi::{c_char, c_int, c_void, CStr, CString};
+use std::mem;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+#[distributed_slice(NUMBERS)]
+static ONE: u32 = 1;
+
+#[distributed_slice(NUMBERS)]
+static TWO: u32 = 2;
+
+#[link(name = "dl")]
+extern "C" {
+    fn dlopen(filename: *const c_char, flags: c_int) -> *mut c_void;
+    fn dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void;
+}
+
+const RTLD_NOW: c_int = 2;
+
+// Builds the Rust plugin into a target directory of its own, since the one
+// running this test is locked, and returns the path of the shared object.
+fn build_rust_plugin() -> PathBuf {
+    let target_dir = Path::new(env!("OUT_DIR")).join("plugin");
+    let status = Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()))
+        .arg("build")
+        .arg("--quiet")
+        .arg("--manifest-path")
+        .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("plugin/Cargo.toml"))
+        .env("CARGO_TARGET_DIR", &target_dir)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    target_dir.join("debug/liblinkme_dynamic_plugin.so")
+}
+
+unsafe fn function<T>(handle: *mut c_void, name: &CStr) -> T {
+    let symbol = unsafe { dlsym(handle, name.as_ptr()) };
+    assert!(!symbol.is_null(), "{name:?}");
+    unsafe { mem::transmute_copy(&symbol) }
+}
+
+fn sorted(numbers: impl IntoIterator<Item = u32>) -> Vec<u32> {
+    let mut numbers = Vec::from_iter(numbers);
+    numbers.sort_unstable();
+    numbers
+}
+
+// Loading the plugin affects the whole process, so everything is checked in
+// one test in order.
+#[test]
+fn test_dynamic() {
+    let mut watcher = Watcher::new(NUMBERS);
+    let images: Vec<_> = unsafe { watcher.poll() }.collect();
+    assert_eq!(images.len(), 1);
+    assert_eq!(images[0].1, [1, 2]);
+    assert_eq!(images[0].0.path(), "/proc/self/exe");
+    assert_eq!(unsafe { watcher.poll() }.len(), 0);
+    assert_eq!(NUMBERS.image().path(), "/proc/self/exe");
+    dynamic::check(NUMBERS).unwrap();
+
+    let path = concat!(env!("OUT_DIR"), "/libplugin.so");
+    let filename = CString::new(path).unwrap();
+    let handle = unsafe { dlopen(filename.as_ptr(), RTLD_NOW) };
+    assert!(!handle.is_null());
+
+    let images: Vec<_> = unsafe { watcher.poll() }.collect();
+    assert_eq!(images.len(), 1);
+    assert_eq!(images[0].0.path(), path);
+    assert_eq!(sorted(images[0].1.iter().copied()), [10, 20]);
+    assert_eq!(unsafe { watcher.poll() }.len(), 0);
+
+    let all = unsafe { dynamic::elements(NUMBERS) };
+    assert_eq!(sorted(all.copied()), [1, 2, 10, 20]);
+    assert_eq!(unsafe { dynamic::images(NUMBERS) }.len(), 2);
+
+    // The slice itself covers only the executable.
+    assert_eq!(sorted(NUMBERS.iter().copied()), [1, 2]);
+    assert_eq!(NUMBERS.image().path(), "/proc/self/exe");
+
+    // Bounds resolved within the plugin cover only the plugin.
+    let plugin_numbers_len: extern "C" fn() -> usize =
+        unsafe { function(handle, c"plugin_numbers_len") };
+    assert_eq!(plugin_numbers_len(), 2);
+
+    let error = dynamic::check(NUMBERS).unwrap_err();
+    assert_eq!(error.image().path(), "/proc/self/exe");
+    assert_eq!(error.strays().len(), 1);
+    assert_eq!(error.strays()[0].0.path(), path);
+    assert_eq!(error.strays()[0].1, 2);
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "distributed slice `NUMBERS` in /proc/self/exe does not include 2 elements in {path}"
+        ),
+    );
+
+    // A plugin built by rustc as a cdylib, whose elements are retained by
+    // #[used] alone and whose version script hides the section's __start_ and
+    // __stop_ symbols under GNU ld and gold.
+    let rust_path = build_rust_plugin();
+    let filename = CString::new(rust_path.to_str().unwrap()).unwrap();
+    let handle = unsafe { dlopen(filename.as_ptr(), RTLD_NOW) };
+    assert!(!handle.is_null());
+
+    let images: Vec<_> = unsafe { watcher.poll() }.collect();
+    assert_eq!(images.len(), 1);
+    assert_eq!(images[0].0.path(), rust_path);
+    assert_eq!(sorted(images[0].1.iter().copied()), [100, 200]);
+
+    let all = unsafe { dynamic::elements(NUMBERS) };
+    assert_eq!(sorted(all.copied()), [1, 2, 10, 20, 100, 200]);
+    assert_eq!(unsafe { dynamic::images(NUMBERS) }.len(), 3);
+    assert_eq!(sorted(NUMBERS.iter().copied()), [1, 2]);
+
+    // The plugin's own copy of the slice covers only the plugin.
+    let rust_plugin_numbers_len: extern "C" fn() -> usize =
+        unsafe { function(handle, c"rust_plugin_numbers_len") };
+    let rust_plugin_numbers_sum: extern "C" fn() -> u32 =
+        unsafe { function(handle, c"rust_plugin_numbers_sum") };
+    let rust_plugin_image_base: extern "C" fn() -> usize =
+        unsafe { function(handle, c"rust_plugin_image_base") };
+    let rust_plugin_strays: extern "C" fn() -> usize =
+        unsafe { function(handle, c"rust_plugin_strays") };
+    assert_eq!(rust_plugin_numbers_len(), 2);
+    assert_eq!(rust_plugin_numbers_sum(), 300);
+    assert_eq!(rust_plugin_image_base(), images[0].0.base());
+    assert_eq!(rust_plugin_strays(), 1);
+
+    let error = dynamic::check(NUMBERS).unwrap_err();
+    assert_eq!(error.strays().len(), 2);
+    assert!(error
+        .strays()
+        .iter()
+        .any(|(image, len)| image.path() == rust_path && *len == 2));
+}
@@ -0,0 +1,68 @@
+#![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+#![allow(unknown_lints, non_local_definitions)] // FIXME
+
+use linkme::distributed_slice;
+
+#[distributed_slice]
+#[linkme(element_info)]
+static HANDLERS: [fn() -> i32];
+
+#[distributed_slice(HANDLERS)]
+static STATIC_HANDLER: fn() -> i32 = || 1;
+
+#[distributed_slice(HANDLERS)]
+fn fn_handler() -> i32 {
+    2
+}
+
+mod nested {
+    use linkme::distributed_slice;
+
+    #[distributed_slice(super::HANDLERS)]
+    fn nested_handler() -> i32 {
+        3
+    }
+}
+
+#[test]
+fn test_entries() {
+    let mut entries: Vec<_> = HANDLERS.entries().unwrap().collect();
+    entries.sort_by_key(|(handler, _info)| handler());
+    assert_eq!(entries.len(), HANDLERS.len());
+
+    let (handler, info) = entries[0];
+    assert_eq!(handler(), 1);
+    assert_eq!(info.name(), "STATIC_HANDLER");
+    assert_eq!(info.module_path(), "element_info");
+    assert_eq!(info.file(), "tests/element_info.rs");
+    assert_eq!(info.line(), 10);
+    assert_eq!(info.position(), None);
+
+    let (handler, info) = entries[1];
+    assert_eq!(handler(), 2);
+    assert_eq!(info.name(), "fn_handler");
+    assert_eq!(info.line(), 13);
+
+    let (handler, info) = entries[2];
+    assert_eq!(handler(), 3);
+    assert_eq!(info.name(), "nested_handler");
+    assert_eq!(info.module_path(), "element_info::nested");
+    assert_eq!(info.line(), 21);
+}
+
+#[test]
+fn test_empty() {
+    #[distributed_slice]
+    #[linkme(element_info)]
+    static EMPTY: [i32];
+
+    assert_eq!(EMPTY.entries().unwrap().len(), 0);
+}
+
+#[test]
+fn test_not_declared() {
+    #[distributed_slice]
+    static PLAIN: [i32];
+
+    assert!(PLAIN.entries().is_none());
+}
@@ -0,0 +1,72 @@
+#![cfg(all(
+    any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "fuchsia",
+        target_os = "freebsd",
+        target_os = "openbsd",
+    ),
+    not(linkme_fallback),
+))]
+#![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+
+use linkme::distributed_slice;
+
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Entry {
+    pub id: u32,
+    pub flags: u32,
+}
+
+// A table assembled outside of linkme, as a C array with a section attribute
+// or an assembly table would be.
+core::arch::global_asm!(
+    ".pushsection lmasmtable,\"awR\"",
+    ".balign 4",
+    ".4byte 1, 0x10",
+    ".4byte 2, 0x20",
+    ".popsection",
+);
+
+#[distributed_slice(unsafe(extern_section = "lmasmtable"))]
+pub static ASM_TABLE: [Entry];
+
+#[test]
+fn test_asm_table() {
+    let mut entries = ASM_TABLE.to_vec();
+    entries.sort_by_key(|entry| entry.id);
+    assert_eq!(
+        entries,
+        [Entry { id: 1, flags: 0x10 }, Entry { id: 2, flags: 0x20 }],
+    );
+}
+
+// The section may also be populated through link_section by hand, alongside
+// elements registered against the declaration.
+#[used]
+#[link_section = "lmrusttable"]
+static BY_HAND: Entry = Entry { id: 3, flags: 0 };
+
+#[distributed_slice(unsafe(extern_section = "lmrusttable"))]
+pub static RUST_TABLE: [Entry];
+
+#[distributed_slice(RUST_TABLE)]
+static REGISTERED: Entry = Entry { id: 4, flags: 0 };
+
+#[test]
+fn test_mixed_table() {
+    let mut ids: Vec<u32> = RUST_TABLE.iter().map(|entry| entry.id).collect();
+    ids.sort_unstable();
+    assert_eq!(ids, [3, 4]);
+}
+
+// Two declarations may view the same foreign table.
+#[distributed_slice(unsafe(extern_section = "lmasmtable"))]
+pub static ASM_TABLE_AGAIN: [Entry];
+
+#[test]
+fn test_shared_table() {
+    assert_eq!(ASM_TABLE_AGAIN.len(), 2);
+    assert_eq!(ASM_TABLE_AGAIN.as_ptr(), ASM_TABLE.as_ptr());
+}
@@ -0,0 +1,52 @@
+#![cfg(all(feature = "fallback", linkme_fallback))]
+#![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+
+use linkme::distributed_slice;
+use std::ptr;
+
+#[distributed_slice]
+#[linkme(element_info)]
+pub static NUMBERS: [u32];
+
+#[distributed_slice(NUMBERS)]
+static ONE: u32 = 1;
+
+#[distributed_slice(NUMBERS)]
+static TWO: u32 = 2;
+
+#[test]
+fn test_materialized_once() {
+    linkme::init();
+
+    let first: &'static [u32] = &NUMBERS;
+    let second: &'static [u32] = &NUMBERS;
+    assert!(ptr::eq(first, second));
+
+    let mut numbers = first.to_vec();
+    numbers.sort_unstable();
+    assert_eq!(numbers, [1, 2]);
+}
+
+#[test]
+fn test_entries_refer_to_slice() {
+    for (element, info) in NUMBERS.entries().unwrap() {
+        let expected = match info.name() {
+            "ONE" => 1,
+            "TWO" => 2,
+            name => panic!("unexpected element {name}"),
+        };
+        assert_eq!(*element, expected);
+        assert!(NUMBERS.iter().any(|number| ptr::eq(number, element)));
+    }
+}
+
+// Without linker support an unsatisfied minimum can only be reported at
+// runtime.
+#[distributed_slice(min = 1)]
+pub static REQUIRED: [u32];
+
+#[test]
+#[should_panic = "#[distributed_slice] with name \"REQUIRED\" has 0 elements, expected at least 1"]
+fn test_min_at_runtime() {
+    let _ = REQUIRED.len();
+}
@@ -23,9 +23,38 @@ unsafe extern "C" fn baz() -> i32 {
     42
 }
 
+#[distributed_slice]
+pub static SLICE4: [fn(u32) -> u32];
+
+#[distributed_slice(SLICE4)]
+const fn double(n: u32) -> u32 {
+    n * 2
+}
+
+#[distributed_slice]
+pub static SLICE5: [fn() -> &'static str];
+
+#[distributed_slice(SLICE5, instantiate(u8, u16, String))]
+fn type_name<T>() -> &'static str
+where
+    T: 'static,
+{
+    std::any::type_name::<T>()
+}
+
 #[test]
 fn test_slices() {
     assert!(!SLICE1.is_empty());
     assert!(!SLICE2.is_empty());
     assert!(!SLICE3.is_empty());
+    assert_eq!(SLICE4[0](21), 42);
+    assert_eq!(double(1), 2);
+}
+
+#[test]
+fn test_instantiate() {
+    let mut names = SLICE5.iter().map(|f| f()).collect::<Vec<_>>();
+    names.sort_unstable();
+    assert_eq!(names, ["alloc::string::String", "u16", "u8"]);
+    assert_eq!(type_name::<i8>(), "i8");
 }
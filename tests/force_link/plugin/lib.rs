@@ -0,0 +1,11 @@
+// Nothing in this crate is referenced by the test, which links it only
+// through force_link!.
+
+use linkme::distributed_slice;
+use linkme_force_link_registry::PLUGINS;
+
+#[distributed_slice(PLUGINS)]
+static JSON: &str = "json";
+
+#[distributed_slice(PLUGINS)]
+static YAML: &str = "yaml";
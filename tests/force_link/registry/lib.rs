@@ -0,0 +1,13 @@
+use linkme::distributed_slice;
+
+#[distributed_slice]
+#[linkme(force_link)]
+pub static PLUGINS: [&str];
+
+#[distributed_slice]
+#[linkme(force_link)]
+pub static HOOKS: [&str];
+
+#[distributed_slice]
+#[linkme(force_link)]
+pub static THEMES: [&str];
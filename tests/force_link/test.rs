@@ -0,0 +1,32 @@
+#![cfg(test)]
+
+use linkme::distributed_slice;
+use linkme_force_link_registry::{self as registry, HOOKS, PLUGINS};
+
+linkme::force_link!(
+    linkme_force_link_plugin => PLUGINS,
+    linkme_force_link_plugin => registry::THEMES,
+);
+
+#[distributed_slice(HOOKS)]
+static LOG: &str = "log";
+
+#[test]
+fn test_force_linked() {
+    let mut plugins = PLUGINS.to_vec();
+    plugins.sort_unstable();
+    assert_eq!(plugins, ["json", "yaml"]);
+}
+
+#[test]
+fn test_not_expected() {
+    // The plugin contributes to PLUGINS only, and nothing expects it to
+    // contribute to HOOKS.
+    assert_eq!(HOOKS[..], ["log"]);
+}
+
+#[test]
+#[should_panic = "#[distributed_slice] with name \"THEMES\" has no elements from force-linked crates: linkme_force_link_plugin"]
+fn test_missing_contribution() {
+    let _ = registry::THEMES.len();
+}
@@ -0,0 +1,12 @@
+use linkme::distributed_slot;
+use linkme_link_error_slots::TIMEOUT;
+
+// Also overrides TIMEOUT.
+linkme::force_link!(linkme_link_error_plugin);
+
+#[distributed_slot(TIMEOUT, override)]
+static SHORT_TIMEOUT: u32 = 3;
+
+fn main() {
+    let _ = *TIMEOUT;
+}
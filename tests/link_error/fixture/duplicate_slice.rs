@@ -0,0 +1,7 @@
+// A second copy of the library's declaration, in a crate of the same name.
+#[path = "lib.rs"]
+mod copy;
+
+fn main() {
+    let _ = duplicate_slice::PLUGINS.len() + copy::PLUGINS.len();
+}
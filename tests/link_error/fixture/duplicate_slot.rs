@@ -0,0 +1,14 @@
+use linkme::distributed_slot;
+use linkme_link_error_slots::CLOCK;
+
+// Also defines CLOCK.
+linkme::force_link!(linkme_link_error_plugin);
+
+#[distributed_slot(CLOCK)]
+fn other_clock() -> u64 {
+    2_000
+}
+
+fn main() {
+    let _ = CLOCK();
+}
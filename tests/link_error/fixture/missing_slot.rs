@@ -0,0 +1,5 @@
+use linkme_link_error_slots::CLOCK;
+
+fn main() {
+    let _ = CLOCK();
+}
@@ -0,0 +1,16 @@
+// Nothing in this crate is referenced by the fixtures, which link it only
+// through force_link!.
+
+use linkme::{distributed_slice, distributed_slot};
+use linkme_link_error_slots::{BACKEND, CLOCK, TIMEOUT};
+
+#[distributed_slot(CLOCK)]
+fn fixed_clock() -> u64 {
+    1_000
+}
+
+#[distributed_slot(TIMEOUT, override)]
+static LONG_TIMEOUT: u32 = 300;
+
+#[distributed_slice(BACKEND)]
+static MEMORY: &str = "memory";
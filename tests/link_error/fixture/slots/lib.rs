@@ -0,0 +1,10 @@
+use linkme::{distributed_slice, distributed_slot};
+
+#[distributed_slot]
+pub static CLOCK: fn() -> u64;
+
+#[distributed_slot]
+pub static TIMEOUT: u32 = 30;
+
+#[distributed_slice(max = 1)]
+pub static BACKEND: [&str];
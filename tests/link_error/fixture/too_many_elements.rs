@@ -0,0 +1,12 @@
+use linkme::distributed_slice;
+use linkme_link_error_slots::BACKEND;
+
+// Also contributes to BACKEND.
+linkme::force_link!(linkme_link_error_plugin);
+
+#[distributed_slice(BACKEND)]
+static DISK: &str = "disk";
+
+fn main() {
+    let _ = BACKEND.len();
+}
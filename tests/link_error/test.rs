@@ -0,0 +1,69 @@
+#![cfg(test)]
+
+use std::env;
+use std::path::Path;
+use std::process::Command;
+
+// Builds one binary of the fixture crate, which must fail to link, and
+// returns the compiler's output.
+fn link_error(bin: &str) -> String {
+    let fixture = Path::new(env!("CARGO_MANIFEST_DIR")).join("fixture");
+    let output = Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()))
+        .arg("build")
+        .arg("--quiet")
+        .arg("--manifest-path")
+        .arg(fixture.join("Cargo.toml"))
+        .arg("--bin")
+        .arg(bin)
+        .env("CARGO_TARGET_DIR", fixture.join("target"))
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+    assert!(!output.status.success(), "{bin} linked successfully");
+    stderr
+}
+
+#[test]
+fn test_duplicate_slice() {
+    let stderr = link_error("duplicate_slice");
+    assert!(
+        stderr.contains("linkme_duplicate_distributed_slice_PLUGINS_"),
+        "{stderr}",
+    );
+}
+
+#[test]
+fn test_missing_slot() {
+    let stderr = link_error("missing_slot");
+    assert!(
+        stderr.contains("linkme_distributed_slot_CLOCK_"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn test_duplicate_slot() {
+    let stderr = link_error("duplicate_slot");
+    assert!(
+        stderr.contains("linkme_distributed_slot_CLOCK_"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn test_duplicate_override() {
+    let stderr = link_error("duplicate_override");
+    assert!(
+        stderr.contains("linkme_distributed_slot_override_TIMEOUT_"),
+        "{stderr}",
+    );
+}
+
+#[test]
+fn test_too_many_elements() {
+    let stderr = link_error("too_many_elements");
+    assert!(
+        stderr.contains("linkme_too_many_elements_distributed_slice_BACKEND_"),
+        "{stderr}",
+    );
+}
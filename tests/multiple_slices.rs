@@ -0,0 +1,62 @@
+#![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+
+use linkme::distributed_slice;
+
+#[distributed_slice]
+pub static COMMANDS: [fn() -> &'static str];
+
+#[distributed_slice]
+pub static HELP_TOPICS: [fn() -> &'static str];
+
+#[distributed_slice]
+pub static TRANSFORMS: [for<'a> fn(&'a str) -> &'a str];
+
+#[distributed_slice]
+pub static STATIC_TRANSFORMS: [fn(&'static str) -> &'static str];
+
+#[distributed_slice]
+pub static NAMES: [&'static str];
+
+#[distributed_slice]
+pub static ALIASES: [&'static str];
+
+#[distributed_slice(COMMANDS, HELP_TOPICS)]
+fn status() -> &'static str {
+    "status"
+}
+
+// Each element takes on the element type of its own slice.
+#[distributed_slice(TRANSFORMS, STATIC_TRANSFORMS)]
+fn trim(s: &str) -> &str {
+    s.trim()
+}
+
+#[distributed_slice(NAMES, ALIASES)]
+static NAME: &str = "linkme";
+
+#[test]
+fn test_fn() {
+    assert_eq!(COMMANDS.len(), 1);
+    assert_eq!(HELP_TOPICS.len(), 1);
+    assert_eq!(COMMANDS[0](), "status");
+    assert_eq!(HELP_TOPICS[0](), "status");
+    assert_eq!(status(), "status");
+}
+
+#[test]
+fn test_element_types() {
+    assert_eq!(TRANSFORMS.len(), 1);
+    assert_eq!(STATIC_TRANSFORMS.len(), 1);
+
+    let borrowed = String::from(" linkme ");
+    let trimmed: &str = TRANSFORMS[0](&borrowed);
+    assert_eq!(trimmed, "linkme");
+    assert_eq!(STATIC_TRANSFORMS[0](" linkme "), "linkme");
+}
+
+#[test]
+fn test_static() {
+    assert_eq!(NAMES[..], ["linkme"]);
+    assert_eq!(ALIASES[..], ["linkme"]);
+    assert_eq!(NAME, "linkme");
+}
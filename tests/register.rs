@@ -0,0 +1,97 @@
+#![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+
+use linkme::distributed_slice;
+
+pub struct Event(u32);
+
+#[distributed_slice]
+pub static HANDLERS: [fn(&Counter, &Event) -> u32];
+
+#[distributed_slice]
+pub static LOGGERS: [fn(&Counter, &Event) -> u32];
+
+#[distributed_slice]
+pub static CONSTRUCTORS: [fn() -> Counter];
+
+pub struct Counter {
+    base: u32,
+}
+
+#[linkme::register]
+impl Counter {
+    const STEP: u32 = 1;
+
+    #[distributed_slice(CONSTRUCTORS)]
+    #[must_use]
+    pub fn with_base() -> Self {
+        Counter { base: 100 }
+    }
+
+    // A private method, registered into two slices by separate attributes.
+    #[distributed_slice(HANDLERS)]
+    #[distributed_slice(LOGGERS)]
+    fn on_event(&self, event: &Event) -> u32 {
+        self.base + event.0 + Self::STEP
+    }
+
+    #[must_use]
+    pub fn unregistered(&self) -> u32 {
+        self.base
+    }
+}
+
+pub trait Format {
+    fn name() -> &'static str;
+}
+
+#[distributed_slice]
+pub static FORMATS: [fn() -> &'static str];
+
+pub struct Json;
+
+pub struct Yaml;
+
+// Takes precedence over the trait's function in `Json::name()`, which is not
+// how the registered impl block below refers to its own function.
+impl Json {
+    #[must_use]
+    pub fn name() -> &'static str {
+        "inherent"
+    }
+}
+
+#[linkme::register]
+impl Format for Json {
+    #[distributed_slice(FORMATS)]
+    fn name() -> &'static str {
+        "json"
+    }
+}
+
+#[linkme::register]
+impl Format for Yaml {
+    #[distributed_slice(FORMATS)]
+    fn name() -> &'static str {
+        "yaml"
+    }
+}
+
+#[test]
+fn test_inherent() {
+    assert_eq!(CONSTRUCTORS.len(), 1);
+    let counter = CONSTRUCTORS[0]();
+    assert_eq!(counter.unregistered(), 100);
+    assert_eq!(HANDLERS.len(), 1);
+    assert_eq!(HANDLERS[0](&counter, &Event(10)), 111);
+    assert_eq!(LOGGERS.len(), 1);
+    assert_eq!(LOGGERS[0](&counter, &Event(20)), 121);
+}
+
+#[test]
+fn test_trait_impl() {
+    let mut names: Vec<&str> = FORMATS.iter().map(|name| name()).collect();
+    names.sort_unstable();
+    assert_eq!(names, ["json", "yaml"]);
+    assert_eq!(Json::name(), "inherent");
+    assert_eq!(<Json as Format>::name(), "json");
+}
@@ -0,0 +1,28 @@
+#![cfg(any(
+    target_os = "uefi",
+    target_os = "windows",
+    all(feature = "fallback", linkme_fallback),
+))]
+#![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+
+use linkme::distributed_slice;
+
+#[distributed_slice]
+static ORDERED: [i32];
+
+#[distributed_slice(ORDERED, 2)]
+static TWO: i32 = 2;
+
+#[distributed_slice(ORDERED)]
+static UNORDERED: i32 = -1;
+
+#[distributed_slice(ORDERED, 10)]
+static TEN: i32 = 10;
+
+#[distributed_slice(ORDERED, 1)]
+static ONE: i32 = 1;
+
+#[test]
+fn test_sort_key() {
+    assert_eq!(*ORDERED, [-1, 1, 2, 10]);
+}
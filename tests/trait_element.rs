@@ -0,0 +1,58 @@
+#![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+
+use linkme::distributed_slice;
+
+pub trait Plugin: Sync {
+    fn name(&self) -> &'static str;
+}
+
+#[distributed_slice]
+pub static PLUGINS: [&'static dyn Plugin];
+
+pub struct Json;
+
+#[distributed_slice(PLUGINS)]
+impl Plugin for Json {
+    fn name(&self) -> &'static str {
+        "json"
+    }
+}
+
+#[distributed_slice(PLUGINS)]
+pub struct Yaml;
+
+impl Plugin for Yaml {
+    fn name(&self) -> &'static str {
+        "yaml"
+    }
+}
+
+pub trait Describe {
+    fn describe(&self) -> &'static str;
+}
+
+#[distributed_slice]
+pub static DESCRIPTIONS: [&'static (dyn Describe + Sync)];
+
+#[distributed_slice(DESCRIPTIONS)]
+impl Describe for Json {
+    fn describe(&self) -> &'static str {
+        "JavaScript Object Notation"
+    }
+}
+
+#[test]
+fn test_impl() {
+    let mut names = PLUGINS
+        .iter()
+        .map(|plugin| plugin.name())
+        .collect::<Vec<_>>();
+    names.sort_unstable();
+    assert_eq!(names, ["json", "yaml"]);
+}
+
+#[test]
+fn test_sync_bound() {
+    assert_eq!(DESCRIPTIONS.len(), 1);
+    assert_eq!(DESCRIPTIONS[0].describe(), "JavaScript Object Notation");
+}
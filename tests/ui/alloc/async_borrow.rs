@@ -0,0 +1,17 @@
+#![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+
+use linkme::distributed_slice;
+use std::future::Future;
+use std::pin::Pin;
+
+pub struct Request;
+
+// The future returned for an element borrowing its arguments is bounded by
+// their lifetime, which this declaration leaves out.
+#[distributed_slice]
+pub static HANDLERS: [fn(&Request) -> Pin<Box<dyn Future<Output = ()> + Send>>];
+
+#[distributed_slice(HANDLERS)]
+async fn handle(_req: &Request) {}
+
+fn main() {}
@@ -0,0 +1,17 @@
+#![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+
+use linkme::distributed_slice;
+
+#[distributed_slice(exact = 1, min = 1)]
+pub static EXACT: [&str];
+
+#[distributed_slice(min = 2, max = 1)]
+pub static INVERTED: [&str];
+
+#[distributed_slice(min = 1, min = 2)]
+pub static DUPLICATE: [&str];
+
+#[distributed_slice(max = 1, most = 2)]
+pub static UNKNOWN: [&str];
+
+fn main() {}
@@ -0,0 +1,19 @@
+#![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+
+use linkme::distributed_slice;
+
+#[distributed_slice(extern_section = "table1")]
+pub static TABLE1: [u32];
+
+#[distributed_slice(unsafe(section = "table2"))]
+pub static TABLE2: [u32];
+
+#[distributed_slice(unsafe(extern_section = "table3"))]
+#[linkme(element_info)]
+pub static TABLE3: [u32];
+
+#[distributed_slice(unsafe(extern_section = "table4"))]
+#[linkme(section = "table4")]
+pub static TABLE4: [u32];
+
+fn main() {}
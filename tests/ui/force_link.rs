@@ -0,0 +1,28 @@
+#![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+
+use linkme::{distributed_map, distributed_slice, distributed_slot};
+
+#[distributed_map]
+#[linkme(force_link)]
+pub static ROUTES: Map<&str, fn() -> u16>;
+
+#[distributed_slot]
+#[linkme(force_link)]
+pub static HANDLER: fn() = || {};
+
+#[distributed_slice(unsafe(extern_section = "plugins"))]
+#[linkme(force_link)]
+pub static PLUGINS: [u32];
+
+linkme::force_link!();
+
+linkme::force_link!(crate);
+
+linkme::force_link!(#[inline] core);
+
+#[distributed_slice]
+pub static PLAIN: [u32];
+
+linkme::force_link!(core => PLAIN);
+
+fn main() {}
@@ -0,0 +1,17 @@
+#![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+
+use linkme::distributed_slice;
+
+#[distributed_slice]
+pub static SLICES: [fn()];
+
+#[distributed_slice(SLICES, instantiate(u8))]
+fn not_generic() {}
+
+#[distributed_slice(SLICES, instantiate(u8))]
+fn two_params<A, B>() {}
+
+#[distributed_slice(SLICES, instantiate())]
+fn no_types<T>() {}
+
+fn main() {}
@@ -0,0 +1,11 @@
+#![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+
+use linkme::distributed_map;
+
+#[distributed_map]
+pub static NOT_A_MAP: [(&'static str, fn())];
+
+#[distributed_map]
+pub static WRONG_ARITY: Map<&'static str>;
+
+fn main() {}
@@ -0,0 +1,14 @@
+#![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+
+use linkme::distributed_slice;
+
+#[distributed_slice]
+pub static NUMBERS: [u32];
+
+#[distributed_slice]
+pub static NAMES: [&'static str];
+
+#[distributed_slice(NUMBERS, NAMES)]
+static ONE: u32 = 1;
+
+fn main() {}
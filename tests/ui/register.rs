@@ -0,0 +1,24 @@
+#![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+
+use linkme::distributed_slice;
+
+#[distributed_slice]
+pub static HANDLERS: [fn()];
+
+pub struct Generic<T>(T);
+
+#[linkme::register]
+impl<T> Generic<T> {
+    #[distributed_slice(HANDLERS)]
+    fn handler() {}
+}
+
+pub struct Plain;
+
+#[linkme::register]
+impl Plain {
+    #[distributed_slice]
+    fn handler() {}
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+#![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+
+use linkme::{distributed_slice, distributed_slot};
+
+#[distributed_slice]
+#[linkme(section = "my.section")]
+pub static SLICE1: [&str];
+
+#[distributed_slice]
+#[linkme(section = "")]
+pub static SLICE2: [&str];
+
+#[distributed_slice]
+#[linkme(section = "a", section = "b")]
+pub static SLICE3: [&str];
+
+#[distributed_slot]
+#[linkme(section = "slot")]
+pub static SLOT: fn();
+
+fn main() {}
@@ -0,0 +1,17 @@
+#![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+
+use linkme::distributed_slot;
+
+#[distributed_slot]
+pub static REQUIRED: u32;
+
+#[distributed_slot(REQUIRED, override)]
+static OVERRIDE_REQUIRED: u32 = 1;
+
+#[distributed_slot]
+pub static DEFAULTED: u32 = 0;
+
+#[distributed_slot(DEFAULTED)]
+static DEFINE_DEFAULTED: u32 = 1;
+
+fn main() {}
@@ -2,9 +2,10 @@
 
 use linkme::distributed_slice;
 
-pub struct Unit;
-
 #[distributed_slice]
-pub static ZEROSIZED: [Unit];
+pub static SLICE: [i32];
+
+#[distributed_slice(SLICE, 1)]
+static ELEMENT: i32 = 1;
 
 fn main() {}
@@ -0,0 +1,18 @@
+#![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+
+use linkme::distributed_slice;
+
+pub trait Plugin: Sync {}
+
+#[distributed_slice]
+pub static PLUGINS: [&'static dyn Plugin];
+
+#[distributed_slice(PLUGINS)]
+impl Plugin for Vec<u8> {}
+
+#[distributed_slice(PLUGINS)]
+pub struct Config {
+    pub verbose: bool,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+// A wasm32-wasip1 command, whose constructors run before main without any
+// call to linkme::init.
+
+use linkme_wasm_test::{check, registered};
+
+fn main() {
+    assert_eq!(registered(), 3);
+    assert_eq!(check(), 0);
+    println!("ok");
+}
@@ -0,0 +1,30 @@
+// A module without any elements, built for wasm32-unknown-unknown and run by
+// ../run.mjs, in which linkme::init is called more than once.
+
+use core::sync::atomic::{AtomicU32, Ordering};
+
+static CONSTRUCTED: AtomicU32 = AtomicU32::new(0);
+
+#[used]
+#[link_section = ".init_array"]
+static CONSTRUCTOR: extern "C" fn() = {
+    extern "C" fn constructor() {
+        CONSTRUCTED.fetch_add(1, Ordering::Relaxed);
+    }
+    constructor
+};
+
+// Bitmask of the checks that failed.
+#[no_mangle]
+pub extern "C" fn check() -> u32 {
+    let mut failed = 0;
+    linkme::init();
+    if CONSTRUCTED.load(Ordering::Relaxed) != 1 {
+        failed |= 1 << 0;
+    }
+    linkme::init();
+    if CONSTRUCTED.load(Ordering::Relaxed) != 1 {
+        failed |= 1 << 1;
+    }
+    failed
+}
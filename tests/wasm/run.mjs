@@ -0,0 +1,28 @@
+// Runs a linkme-wasm-test module under Node:
+//
+//     node run.mjs path/to/linkme-wasm-test.wasm
+//     node run.mjs path/to/linkme_wasm_test.wasm
+//     node run.mjs path/to/linkme_wasm_empty_test.wasm
+//
+// The first is the wasm32-wasip1 command, which is started through WASI and
+// checks itself. The second is the library, for either target, which is only
+// instantiated and so relies on linkme::init to run its constructors. The
+// module of linkme-wasm-empty-test is run the same way as the library.
+
+import assert from 'node:assert/strict';
+import { readFile } from 'node:fs/promises';
+import { WASI } from 'node:wasi';
+
+const module = await WebAssembly.compile(await readFile(process.argv[2]));
+const wasi = new WASI({ version: 'preview1', returnOnExit: true });
+const instance = await WebAssembly.instantiate(module, wasi.getImportObject());
+
+if (instance.exports._start) {
+  assert.equal(wasi.start(instance), 0);
+} else {
+  assert.equal(instance.exports.check(), 0);
+  if (instance.exports.registered) {
+    assert.equal(instance.exports.registered(), 3);
+  }
+  console.log('ok');
+}
@@ -0,0 +1,76 @@
+// Built for wasm32-unknown-unknown and wasm32-wasip1, and run by run.mjs.
+
+use linkme::{distributed_map, distributed_slice, distributed_slot};
+
+#[distributed_slice]
+pub static NUMBERS: [u32];
+
+#[distributed_slice(NUMBERS)]
+static ONE: u32 = 1;
+
+#[distributed_slice(NUMBERS)]
+static TWO: u32 = 2;
+
+#[distributed_slice(NUMBERS)]
+static THREE: u32 = 3;
+
+// Positioned elements are sorted by the fallback backend. The crate is also
+// built natively as part of the workspace, where ELF cannot sort them.
+#[distributed_slice]
+pub static ORDERED: [u32];
+
+#[cfg(target_family = "wasm")]
+#[distributed_slice(ORDERED, 2)]
+static SECOND: u32 = 2;
+
+#[cfg(target_family = "wasm")]
+#[distributed_slice(ORDERED, 1)]
+static FIRST: u32 = 1;
+
+#[distributed_map]
+pub static STATUS: Map<&str, u32>;
+
+#[distributed_map(STATUS, key = "ok")]
+static OK: u32 = 200;
+
+#[distributed_map(STATUS, key = "teapot")]
+static TEAPOT: u32 = 418;
+
+#[distributed_slot]
+pub static ANSWER: u32 = 0;
+
+#[distributed_slot(ANSWER, override)]
+static FORTY_TWO: u32 = 42;
+
+// The number of elements registered before any call to `linkme::init`.
+#[no_mangle]
+#[allow(clippy::cast_possible_truncation)]
+pub extern "C" fn registered() -> u32 {
+    NUMBERS.len() as u32
+}
+
+// Bitmask of the checks that failed.
+#[no_mangle]
+pub extern "C" fn check() -> u32 {
+    linkme::init();
+
+    let mut failed = 0;
+    let mut numbers = NUMBERS.to_vec();
+    numbers.sort_unstable();
+    if numbers != [1, 2, 3] {
+        failed |= 1 << 0;
+    }
+    if STATUS.get("ok") != Some(&200) || STATUS.get("teapot") != Some(&418) {
+        failed |= 1 << 1;
+    }
+    if STATUS.get("missing").is_some() {
+        failed |= 1 << 2;
+    }
+    if *ANSWER != 42 || !ANSWER.is_overridden() {
+        failed |= 1 << 3;
+    }
+    if cfg!(target_family = "wasm") && ORDERED[..] != [1, 2] {
+        failed |= 1 << 4;
+    }
+    failed
+}
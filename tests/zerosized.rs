@@ -0,0 +1,70 @@
+#![cfg(not(windows))]
+#![cfg_attr(feature = "used_linker", feature(used_with_arg))]
+#![allow(unknown_lints, non_local_definitions)] // FIXME
+
+use linkme::{distributed_slice, distributed_slot};
+
+#[derive(Debug, PartialEq)]
+pub struct Feature;
+
+#[distributed_slice]
+pub static FEATURES: [Feature];
+
+#[distributed_slice(FEATURES)]
+static JSON: Feature = Feature;
+
+#[distributed_slice(FEATURES)]
+static YAML: Feature = Feature;
+
+mod nested {
+    use linkme::distributed_slice;
+
+    #[distributed_slice(super::FEATURES)]
+    static TOML: super::Feature = super::Feature;
+}
+
+#[test]
+fn test_unit_struct() {
+    assert_eq!(FEATURES.len(), 3);
+    assert_eq!(FEATURES.iter().count(), 3);
+    assert_eq!(FEATURES[2], Feature);
+    assert!(FEATURES.get(3).is_none());
+}
+
+#[test]
+fn test_empty() {
+    #[distributed_slice]
+    static EMPTY: [()];
+
+    assert!(EMPTY.is_empty());
+}
+
+#[test]
+fn test_overaligned() {
+    #[distributed_slice]
+    static ALIGNED: [[u64; 0]];
+
+    #[distributed_slice(ALIGNED)]
+    static A: [u64; 0] = [];
+
+    #[distributed_slice(ALIGNED)]
+    static B: [u64; 0] = [];
+
+    assert_eq!(ALIGNED.len(), 2);
+    assert_eq!(ALIGNED.as_ptr() as usize % 8, 0);
+}
+
+#[test]
+fn test_slot_override() {
+    #[distributed_slot]
+    static DEFAULTED: () = ();
+
+    #[distributed_slot]
+    static OVERRIDDEN: () = ();
+
+    #[distributed_slot(OVERRIDDEN, override)]
+    static OVERRIDE: () = ();
+
+    assert!(!DEFAULTED.is_overridden());
+    assert!(OVERRIDDEN.is_overridden());
+}